@@ -0,0 +1,350 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::ops::{Add, Div};
+
+use crate::dataframe::Group;
+
+/// Aggregation computed in a single pass over the rows of a group.
+/// Aggregators are combined with tuples, e.g. `(Count, Sum::of(|x| x.age))`, so several results are computed
+/// in the same pass, and their partial states can be merged to aggregate chunks of a group separately.
+/// Example:
+/// ```
+/// use combee::{aggregators::{Aggregator, Count, Max, Mean, Sum}, dataframe::DataFrame};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: u32
+/// }
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct Stats {
+///     name: String,
+///     count: usize,
+///     total: u32,
+///     mean: f64,
+///     oldest: Option<u32>
+/// }
+///
+/// let df = DataFrame::new(vec![
+///     D { name: "a".to_string(), age: 26 },
+///     D { name: "b".to_string(), age: 40 },
+///     D { name: "a".to_string(), age: 30 }
+/// ]);
+///
+/// let stats = df.groupby(|d| d.name.clone()).agg(|name, g| {
+///     (Count, Sum::of(|d: &D| d.age), Mean::of(|d: &D| d.age as f64), Max::of(|d: &D| d.age))
+///         .map(|(count, total, mean, oldest)| Stats { name: name.clone(), count, total, mean, oldest })
+///         .aggregate(g)
+/// });
+///
+/// let a = stats.find(|s| s.name == "a").unwrap();
+/// assert_eq!((a.count, a.total, a.mean, a.oldest), (2, 56, 28.0, Some(30)));
+/// ```
+pub trait Aggregator<D> {
+    /// Partial result of the aggregation.
+    type State: Clone;
+
+    /// Final result of the aggregation.
+    type Output;
+
+    /// State of an empty group.
+    fn init(&self) -> Self::State;
+
+    /// Adds a row to the state.
+    fn update(&self, state: &mut Self::State, row: &D);
+
+    /// Adds the state of other rows of the same group to the state.
+    fn merge(&self, state: &mut Self::State, other: Self::State);
+
+    /// Result of the aggregation of the rows of the state.
+    fn finish(&self, state: Self::State) -> Self::Output;
+
+    /// State of the rows, to be merged with the states of the other rows of the group.
+    fn accumulate<'r, It>(&self, rows: It) -> Self::State
+    where
+        D: 'r,
+        It: IntoIterator<Item = &'r D>,
+    {
+        let mut state = self.init();
+        for row in rows {
+            self.update(&mut state, row);
+        }
+        state
+    }
+
+    /// Result of the aggregation of the rows of a group.
+    fn aggregate(&self, group: &Group<D>) -> Self::Output
+    where
+        D: Clone + DeserializeOwned + Serialize,
+    {
+        self.finish(self.accumulate(group.iter()))
+    }
+
+    /// Aggregator whose result is the function applied to the result of this one, e.g. to fill a struct.
+    fn map<O, M>(self, func: M) -> Map<Self, M>
+    where
+        Self: Sized,
+        M: Fn(Self::Output) -> O,
+    {
+        Map {
+            aggregator: self,
+            func,
+        }
+    }
+}
+
+/// Number of rows.
+#[derive(Clone, Copy, Debug)]
+pub struct Count;
+
+impl<D> Aggregator<D> for Count {
+    type State = usize;
+    type Output = usize;
+
+    fn init(&self) -> usize {
+        0
+    }
+
+    fn update(&self, state: &mut usize, _: &D) {
+        *state += 1;
+    }
+
+    fn merge(&self, state: &mut usize, other: usize) {
+        *state += other;
+    }
+
+    fn finish(&self, state: usize) -> usize {
+        state
+    }
+}
+
+/// Sum of the values of the property, the default value for an empty group.
+#[derive(Clone, Copy, Debug)]
+pub struct Sum<F> {
+    property: F,
+}
+
+impl<F> Sum<F> {
+    /// Instantiate a sum of the values of the property.
+    pub fn of(property: F) -> Self {
+        Sum { property }
+    }
+}
+
+impl<D, N, F> Aggregator<D> for Sum<F>
+where
+    N: Add<Output = N> + Default + Clone,
+    F: Fn(&D) -> N,
+{
+    type State = N;
+    type Output = N;
+
+    fn init(&self) -> N {
+        N::default()
+    }
+
+    fn update(&self, state: &mut N, row: &D) {
+        *state = state.clone() + (self.property)(row);
+    }
+
+    fn merge(&self, state: &mut N, other: N) {
+        *state = state.clone() + other;
+    }
+
+    fn finish(&self, state: N) -> N {
+        state
+    }
+}
+
+/// Average of the values of the property, like [crate::functions::avg].
+#[derive(Clone, Copy, Debug)]
+pub struct Mean<F> {
+    property: F,
+}
+
+impl<F> Mean<F> {
+    /// Instantiate an average of the values of the property.
+    pub fn of(property: F) -> Self {
+        Mean { property }
+    }
+}
+
+impl<D, N, F> Aggregator<D> for Mean<F>
+where
+    N: Div<f64, Output = N> + Add<Output = N> + Default + Clone,
+    F: Fn(&D) -> N,
+{
+    type State = (N, f64);
+    type Output = N;
+
+    fn init(&self) -> (N, f64) {
+        (N::default(), 0.0)
+    }
+
+    fn update(&self, state: &mut (N, f64), row: &D) {
+        state.0 = state.0.clone() + (self.property)(row);
+        state.1 += 1.0;
+    }
+
+    fn merge(&self, state: &mut (N, f64), other: (N, f64)) {
+        state.0 = state.0.clone() + other.0;
+        state.1 += other.1;
+    }
+
+    fn finish(&self, state: (N, f64)) -> N {
+        state.0 / state.1
+    }
+}
+
+/// Maximum value of the property, None for an empty group.
+#[derive(Clone, Copy, Debug)]
+pub struct Max<F> {
+    property: F,
+}
+
+impl<F> Max<F> {
+    /// Instantiate a maximum of the values of the property.
+    pub fn of(property: F) -> Self {
+        Max { property }
+    }
+}
+
+impl<D, N, F> Aggregator<D> for Max<F>
+where
+    N: Ord + Clone,
+    F: Fn(&D) -> N,
+{
+    type State = Option<N>;
+    type Output = Option<N>;
+
+    fn init(&self) -> Option<N> {
+        None
+    }
+
+    fn update(&self, state: &mut Option<N>, row: &D) {
+        self.merge(state, Some((self.property)(row)));
+    }
+
+    fn merge(&self, state: &mut Option<N>, other: Option<N>) {
+        if let Some(value) = other {
+            match state {
+                Some(current) if *current >= value => {}
+                _ => *state = Some(value),
+            }
+        }
+    }
+
+    fn finish(&self, state: Option<N>) -> Option<N> {
+        state
+    }
+}
+
+/// Minimum value of the property, None for an empty group.
+#[derive(Clone, Copy, Debug)]
+pub struct Min<F> {
+    property: F,
+}
+
+impl<F> Min<F> {
+    /// Instantiate a minimum of the values of the property.
+    pub fn of(property: F) -> Self {
+        Min { property }
+    }
+}
+
+impl<D, N, F> Aggregator<D> for Min<F>
+where
+    N: Ord + Clone,
+    F: Fn(&D) -> N,
+{
+    type State = Option<N>;
+    type Output = Option<N>;
+
+    fn init(&self) -> Option<N> {
+        None
+    }
+
+    fn update(&self, state: &mut Option<N>, row: &D) {
+        self.merge(state, Some((self.property)(row)));
+    }
+
+    fn merge(&self, state: &mut Option<N>, other: Option<N>) {
+        if let Some(value) = other {
+            match state {
+                Some(current) if *current <= value => {}
+                _ => *state = Some(value),
+            }
+        }
+    }
+
+    fn finish(&self, state: Option<N>) -> Option<N> {
+        state
+    }
+}
+
+/// Aggregator that applies a function to the result of another one, see [Aggregator::map].
+#[derive(Clone, Copy, Debug)]
+pub struct Map<A, M> {
+    aggregator: A,
+    func: M,
+}
+
+impl<D, O, A, M> Aggregator<D> for Map<A, M>
+where
+    A: Aggregator<D>,
+    M: Fn(A::Output) -> O,
+{
+    type State = A::State;
+    type Output = O;
+
+    fn init(&self) -> A::State {
+        self.aggregator.init()
+    }
+
+    fn update(&self, state: &mut A::State, row: &D) {
+        self.aggregator.update(state, row)
+    }
+
+    fn merge(&self, state: &mut A::State, other: A::State) {
+        self.aggregator.merge(state, other)
+    }
+
+    fn finish(&self, state: A::State) -> O {
+        (self.func)(self.aggregator.finish(state))
+    }
+}
+
+macro_rules! impl_tuple_aggregator {
+    ($($name:ident $index:tt),+) => {
+        impl<D, $($name: Aggregator<D>),+> Aggregator<D> for ($($name,)+) {
+            type State = ($($name::State,)+);
+            type Output = ($($name::Output,)+);
+
+            fn init(&self) -> Self::State {
+                ($(self.$index.init(),)+)
+            }
+
+            fn update(&self, state: &mut Self::State, row: &D) {
+                $(self.$index.update(&mut state.$index, row);)+
+            }
+
+            fn merge(&self, state: &mut Self::State, other: Self::State) {
+                $(self.$index.merge(&mut state.$index, other.$index);)+
+            }
+
+            fn finish(&self, state: Self::State) -> Self::Output {
+                ($(self.$index.finish(state.$index),)+)
+            }
+        }
+    };
+}
+
+impl_tuple_aggregator!(A 0);
+impl_tuple_aggregator!(A 0, B 1);
+impl_tuple_aggregator!(A 0, B 1, C 2);
+impl_tuple_aggregator!(A 0, B 1, C 2, E 3);
+impl_tuple_aggregator!(A 0, B 1, C 2, E 3, G 4);
+impl_tuple_aggregator!(A 0, B 1, C 2, E 3, G 4, H 5);
+impl_tuple_aggregator!(A 0, B 1, C 2, E 3, G 4, H 5, J 6);
+impl_tuple_aggregator!(A 0, B 1, C 2, E 3, G 4, H 5, J 6, K 7);
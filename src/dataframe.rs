@@ -1,4 +1,5 @@
 use std::{
+    cell::OnceCell,
     cmp::{Eq, Ordering},
     collections::HashMap,
     fmt::{self, Debug},
@@ -10,13 +11,14 @@ use arrow2::{
     self,
     array::Array,
     chunk::Chunk,
+    datatypes::DataType,
+    datatypes::Field,
     datatypes::Schema,
-    io::parquet::write::{
-        transverse, CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version,
-        WriteOptions,
+    io::{
+        ipc,
+        parquet::write::{transverse, Encoding, FileWriter, RowGroupIterator, WriteOptions},
     },
 };
-use csv;
 use log;
 use rand::seq::SliceRandom;
 use serde::{de::DeserializeOwned, Serialize};
@@ -25,9 +27,23 @@ use serde_arrow::{
     schema::TracingOptions,
 };
 
-use std::fs::File;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
 
-use crate::errors;
+use crate::{
+    csv_serializer::Formatted,
+    decimal::{decimal_fit, decimal_values, unscaled_array, DECIMAL_NAME},
+    errors,
+    join::{asof_join, hash_join, key_filter, AsofDirection, AsofKey, JoinType},
+    options::{CsvWriteOptions, IpcFormat, IpcWriteOptions, ParquetWriteOptions},
+    partition::{partition_dir, partition_values},
+    probe::marked_fields,
+    sample::sample,
+    temporal::{temporal_array, Temporal},
+};
 
 /// A DataFrame is the main data structure of combee.
 pub struct DataFrame<D: Clone + DeserializeOwned + Serialize> {
@@ -53,6 +69,15 @@ where
 {
     dataframe: &'a DataFrame<D>,
     index: F,
+    groups: OnceCell<GroupIndex<'a, D, I>>,
+}
+
+/// Rows of each group, with the groups in order of first appearance.
+struct GroupIndex<'a, D, I> {
+    groups: Vec<(I, Vec<&'a D>)>,
+    positions: HashMap<I, usize>,
+    /// Position of the group of each row of the DataFrame.
+    row_groups: Vec<usize>,
 }
 
 impl<D: Clone + DeserializeOwned + Serialize> DataFrame<D> {
@@ -165,6 +190,232 @@ impl<D: Clone + DeserializeOwned + Serialize> DataFrame<D> {
         GroupedDataFrame::new(self, index)
     }
 
+    /// Join the rows of two DataFrames with equal keys (hash join), the kind of join sets the type of the rows:
+    /// - [Inner](crate::join::Inner): `(D, E)`, only rows with a match on both sides.
+    /// - [Left](crate::join::Left): `(D, Option<E>)`, every row of this DataFrame.
+    /// - [Right](crate::join::Right): `(Option<D>, E)`, every row of the other DataFrame.
+    /// - [Outer](crate::join::Outer): `(Option<D>, Option<E>)`, every row of both DataFrames.
+    ///
+    /// Rows keep the order of this DataFrame, unmatched rows of the other DataFrame come last.
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, join::{Inner, Left}};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct User {
+    ///     id: u32,
+    ///     name: String
+    /// }
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct Order {
+    ///     user_id: u32,
+    ///     amount: f64
+    /// }
+    ///
+    /// let users = DataFrame::new(vec![
+    ///     User {id: 1, name: "Daniel".to_string()},
+    ///     User {id: 2, name: "Sergio".to_string()}
+    /// ]);
+    /// let orders = DataFrame::new(vec![
+    ///     Order {user_id: 1, amount: 10.0},
+    ///     Order {user_id: 1, amount: 2.5}
+    /// ]);
+    ///
+    /// let joined = users.join(&orders, |u| u.id, |o| o.user_id, Inner);
+    /// assert_eq!(joined.len(), 2);
+    ///
+    /// let joined = users.join(&orders, |u| u.id, |o| o.user_id, Left);
+    /// assert_eq!(joined.len(), 3);
+    /// assert!(joined.find(|(u, o)| u.id == 2 && o.is_none()).is_some());
+    /// ```
+    pub fn join<E, I, FL, FR, J>(
+        &self,
+        other: &DataFrame<E>,
+        left_key: FL,
+        right_key: FR,
+        _how: J,
+    ) -> DataFrame<J::Output>
+    where
+        E: Clone + DeserializeOwned + Serialize,
+        I: Eq + Hash,
+        FL: Fn(&D) -> I,
+        FR: Fn(&E) -> I,
+        J: JoinType<D, E>,
+    {
+        DataFrame::new(hash_join::<D, E, I, FL, FR, J>(
+            &self.data,
+            &other.data,
+            left_key,
+            right_key,
+        ))
+    }
+
+    /// As-of join: every row with the row of the other DataFrame with the closest key in the given direction,
+    /// or None if there is no such row within the tolerance (left join semantics).
+    /// Keys are [AsofKey]s, e.g. integers, floats or `time` dates and datetimes (with a `time::Duration`
+    /// tolerance); keys that are not comparable (NaN) never match and distances that overflow are outside
+    /// any tolerance. The rows keep the order of this DataFrame and the other DataFrame does not need to be
+    /// sorted; on equal keys the last row is used for backward joins and the first one for forward joins.
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, join::AsofDirection};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct Trade {
+    ///     time: i64,
+    ///     price: f64
+    /// }
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct Quote {
+    ///     time: i64,
+    ///     bid: f64
+    /// }
+    ///
+    /// let trades = DataFrame::new(vec![Trade {time: 5, price: 10.1}, Trade {time: 20, price: 10.4}]);
+    /// let quotes = DataFrame::new(vec![Quote {time: 1, bid: 10.0}, Quote {time: 4, bid: 10.05}]);
+    ///
+    /// let joined = trades.join_asof(&quotes, |t| t.time, |q| q.time, AsofDirection::Backward, Some(10));
+    /// let rows = joined.take(2);
+    /// assert_eq!(rows[0].1.as_ref().unwrap().bid, 10.05);
+    /// assert!(rows[1].1.is_none());
+    /// ```
+    pub fn join_asof<E, K, FL, FR>(
+        &self,
+        other: &DataFrame<E>,
+        left_on: FL,
+        right_on: FR,
+        direction: AsofDirection,
+        tolerance: Option<K::Distance>,
+    ) -> DataFrame<(D, Option<E>)>
+    where
+        E: Clone + DeserializeOwned + Serialize,
+        K: AsofKey,
+        FL: Fn(&D) -> K,
+        FR: Fn(&E) -> K,
+    {
+        self.join_asof_by(
+            other,
+            |_| (),
+            |_| (),
+            left_on,
+            right_on,
+            direction,
+            tolerance,
+        )
+    }
+
+    /// As-of join that only matches rows with equal `by` keys, e.g. the last quote of the same symbol.
+    /// See [DataFrame::join_asof].
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, join::AsofDirection};
+    ///
+    /// let trades = DataFrame::new(vec![('A', 10), ('B', 10)]);
+    /// let quotes = DataFrame::new(vec![('A', 8, 1.5), ('B', 2, 7.0), ('A', 11, 1.6)]);
+    ///
+    /// let joined = trades.join_asof_by(
+    ///     &quotes,
+    ///     |t| t.0,
+    ///     |q| q.0,
+    ///     |t| t.1,
+    ///     |q| q.1,
+    ///     AsofDirection::Nearest,
+    ///     None::<i32>,
+    /// );
+    /// let rows = joined.take(2);
+    /// assert_eq!(rows[0].1, Some(('A', 11, 1.6)));
+    /// assert_eq!(rows[1].1, Some(('B', 2, 7.0)));
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn join_asof_by<E, I, K, BL, BR, FL, FR>(
+        &self,
+        other: &DataFrame<E>,
+        left_by: BL,
+        right_by: BR,
+        left_on: FL,
+        right_on: FR,
+        direction: AsofDirection,
+        tolerance: Option<K::Distance>,
+    ) -> DataFrame<(D, Option<E>)>
+    where
+        E: Clone + DeserializeOwned + Serialize,
+        I: Eq + Hash,
+        K: AsofKey,
+        BL: Fn(&D) -> I,
+        BR: Fn(&E) -> I,
+        FL: Fn(&D) -> K,
+        FR: Fn(&E) -> K,
+    {
+        DataFrame::new(asof_join(
+            &self.data,
+            &other.data,
+            left_by,
+            right_by,
+            left_on,
+            right_on,
+            direction,
+            tolerance,
+        ))
+    }
+
+    /// Keep the rows whose key appears in the other DataFrame (semi join), each row at most once.
+    /// Example:
+    /// ```
+    /// use combee::dataframe::DataFrame;
+    ///
+    /// let df = DataFrame::new(vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+    /// let other = DataFrame::new(vec![3, 1, 1]);
+    ///
+    /// let kept = df.semi_join(&other, |r| r.0, |k| *k);
+    /// assert_eq!(kept.take(2), vec![(1, 'a'), (3, 'c')]);
+    /// ```
+    pub fn semi_join<E, I, FL, FR>(&self, other: &DataFrame<E>, left_key: FL, right_key: FR) -> Self
+    where
+        E: Clone + DeserializeOwned + Serialize,
+        I: Eq + Hash,
+        FL: Fn(&D) -> I,
+        FR: Fn(&E) -> I,
+    {
+        DataFrame::new(key_filter(
+            &self.data,
+            &other.data,
+            left_key,
+            right_key,
+            true,
+        ))
+    }
+
+    /// Keep the rows whose key does not appear in the other DataFrame (anti join).
+    /// Example:
+    /// ```
+    /// use combee::dataframe::DataFrame;
+    ///
+    /// let df = DataFrame::new(vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+    /// let other = DataFrame::new(vec![3, 1, 1]);
+    ///
+    /// let kept = df.anti_join(&other, |r| r.0, |k| *k);
+    /// assert_eq!(kept.take(2), vec![(2, 'b')]);
+    /// ```
+    pub fn anti_join<E, I, FL, FR>(&self, other: &DataFrame<E>, left_key: FL, right_key: FR) -> Self
+    where
+        E: Clone + DeserializeOwned + Serialize,
+        I: Eq + Hash,
+        FL: Fn(&D) -> I,
+        FR: Fn(&E) -> I,
+    {
+        DataFrame::new(key_filter(
+            &self.data,
+            &other.data,
+            left_key,
+            right_key,
+            false,
+        ))
+    }
+
     /// Find a row in the dataframe that matches a condition.
     pub fn find<F>(&self, condition: F) -> Option<&D>
     where
@@ -211,51 +462,437 @@ impl<D: Clone + DeserializeOwned + Serialize> DataFrame<D> {
 
     /// Save a DataFrame as a CSV file.
     pub fn to_csv(&self, path: String) -> Result<(), errors::Error> {
+        self.to_csv_with(path, &CsvWriteOptions::default())
+    }
+
+    /// Save a DataFrame as a CSV file using custom options (delimiter, quoting, header, append...).
+    /// Example:
+    /// ```no_run
+    /// use combee::{dataframe::DataFrame, options::{CsvWriteOptions, QuoteStyle}};
+    ///
+    /// let df = DataFrame::new(vec![(1, 0.123), (2, 4.0)]);
+    ///
+    /// let options = CsvWriteOptions::new()
+    ///     .delimiter(b'\t')
+    ///     .quote_style(QuoteStyle::Never)
+    ///     .float_precision(Some(2));
+    /// df.to_csv_with("dataset.tsv".to_string(), &options).unwrap();
+    /// ```
+    pub fn to_csv_with(
+        &self,
+        path: String,
+        options: &CsvWriteOptions,
+    ) -> Result<(), errors::Error> {
         log::debug!("Saving DataFrame to CSV file at path: {}", path);
+        write_csv_file(&path, &self.data, options)
+    }
 
-        let mut writer = match csv::Writer::from_path(path) {
-            Ok(w) => w,
-            Err(e) => {
+    /// Write a DataFrame as CSV to any writer (stdout, in-memory buffers, compressed streams...).
+    /// The append option is ignored, the rows are written at the current position of the writer.
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, options::CsvWriteOptions};
+    ///
+    /// let df = DataFrame::new(vec![(1, 0.5), (2, 4.0)]);
+    ///
+    /// let mut buffer = Vec::new();
+    /// df.write_csv(&mut buffer, &CsvWriteOptions::default()).unwrap();
+    /// assert_eq!(String::from_utf8(buffer).unwrap(), "1,0.5\n2,4.0\n");
+    /// ```
+    pub fn write_csv<W: Write>(
+        &self,
+        writer: W,
+        options: &CsvWriteOptions,
+    ) -> Result<(), errors::Error> {
+        write_csv_rows(writer, &self.data, options.writer_builder(), options)
+    }
+
+    /// Save a DataFrame as a JSON file containing an array with one object per row.
+    pub fn to_json(&self, path: String) -> Result<(), errors::Error> {
+        log::debug!("Saving DataFrame to JSON file at path: {}", path);
+        write_json_rows(create_file(&path)?, &self.data)
+    }
+
+    /// Write a DataFrame as a JSON array to any writer.
+    /// Example:
+    /// ```
+    /// use combee::dataframe::DataFrame;
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct D {
+    ///     name: String,
+    ///     age: u32
+    /// }
+    ///
+    /// let df = DataFrame::new(vec![D { name: "Daniel".to_string(), age: 26 }]);
+    ///
+    /// let mut buffer = Vec::new();
+    /// df.write_json(&mut buffer).unwrap();
+    /// assert_eq!(String::from_utf8(buffer).unwrap(), "[\n{\"name\":\"Daniel\",\"age\":26}\n]\n");
+    /// ```
+    pub fn write_json<W: Write>(&self, writer: W) -> Result<(), errors::Error> {
+        write_json_rows(writer, &self.data)
+    }
+
+    /// Save a DataFrame as a newline delimited JSON file, one object per line.
+    pub fn to_ndjson(&self, path: String) -> Result<(), errors::Error> {
+        log::debug!("Saving DataFrame to NDJSON file at path: {}", path);
+        write_ndjson_rows(create_file(&path)?, &self.data)
+    }
+
+    /// Write a DataFrame as newline delimited JSON to any writer.
+    /// Example:
+    /// ```
+    /// use combee::dataframe::DataFrame;
+    ///
+    /// let df = DataFrame::new(vec![(1, true), (2, false)]);
+    ///
+    /// let mut buffer = Vec::new();
+    /// df.write_ndjson(&mut buffer).unwrap();
+    /// assert_eq!(String::from_utf8(buffer).unwrap(), "[1,true]\n[2,false]\n");
+    /// ```
+    pub fn write_ndjson<W: Write>(&self, writer: W) -> Result<(), errors::Error> {
+        write_ndjson_rows(writer, &self.data)
+    }
+
+    /// Save a DataFrame as a Parquet file.
+    pub fn to_parquet(&self, path: String) -> Result<(), errors::Error> {
+        self.to_parquet_with(path, &ParquetWriteOptions::default())
+    }
+
+    /// Save a DataFrame as a Parquet file using custom options (compression, statistics, row group size...).
+    /// Example:
+    /// ```no_run
+    /// use combee::{dataframe::DataFrame, options::{ParquetCompression, ParquetWriteOptions}};
+    ///
+    /// let df = DataFrame::new(vec![(1, 0.5), (2, 4.0)]);
+    ///
+    /// let options = ParquetWriteOptions::new()
+    ///     .compression(ParquetCompression::Snappy)
+    ///     .row_group_size(Some(1));
+    /// df.to_parquet_with("dataset.parquet".to_string(), &options).unwrap();
+    /// ```
+    pub fn to_parquet_with(
+        &self,
+        path: String,
+        options: &ParquetWriteOptions,
+    ) -> Result<(), errors::Error> {
+        log::debug!("Saving DataFrame to Parquet file at path: {}", path);
+
+        match File::create(&path) {
+            Ok(file) => {
+                let result = self.write_parquet_with(file, options);
+                // A failed write would leave a truncated file behind.
+                if result.is_err() {
+                    let _ = std::fs::remove_file(&path);
+                }
+                result
+            }
+            Err(e) => Err(errors::Error {
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    /// Write a DataFrame as Parquet to any writer (stdout, in-memory buffers...).
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, read_parquet_from_bytes};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct D {
+    ///     name: String,
+    ///     age: u32
+    /// }
+    ///
+    /// let df = DataFrame::new(vec![D { name: "Daniel".to_string(), age: 26 }]);
+    ///
+    /// let mut buffer = Vec::new();
+    /// df.write_parquet(&mut buffer).unwrap();
+    ///
+    /// let new_df = read_parquet_from_bytes::<D, _>(buffer).unwrap();
+    /// assert_eq!(new_df.len(), 1);
+    /// ```
+    pub fn write_parquet<W: Write>(&self, writer: W) -> Result<(), errors::Error> {
+        self.write_parquet_with(writer, &ParquetWriteOptions::default())
+    }
+
+    /// Write a DataFrame as Parquet to any writer using custom options.
+    pub fn write_parquet_with<W: Write>(
+        &self,
+        writer: W,
+        options: &ParquetWriteOptions,
+    ) -> Result<(), errors::Error> {
+        let fields = parquet_fields(&self.data, options, false)?;
+        write_parquet_rows(writer, &self.data, &fields, options, &[])
+    }
+
+    /// Save a DataFrame as an Arrow IPC file (Feather v2).
+    pub fn to_ipc(&self, path: String) -> Result<(), errors::Error> {
+        self.to_ipc_with(path, &IpcWriteOptions::default())
+    }
+
+    /// Save a DataFrame as Arrow IPC using custom options (file or stream format, compression, batch size...).
+    /// Example:
+    /// ```no_run
+    /// use combee::{dataframe::DataFrame, options::{IpcCompression, IpcFormat, IpcWriteOptions}};
+    ///
+    /// let df = DataFrame::new(vec![(1, 2.0), (2, 3.5)]);
+    ///
+    /// let options = IpcWriteOptions::new()
+    ///     .format(IpcFormat::Stream)
+    ///     .compression(IpcCompression::Zstd);
+    /// df.to_ipc_with("dataset.arrows".to_string(), &options).unwrap();
+    /// ```
+    pub fn to_ipc_with(
+        &self,
+        path: String,
+        options: &IpcWriteOptions,
+    ) -> Result<(), errors::Error> {
+        log::debug!("Saving DataFrame to Arrow IPC file at path: {}", path);
+        write_ipc_rows(create_file(&path)?, &self.data, options)
+    }
+
+    /// Write a DataFrame as an Arrow IPC file to any writer.
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, read_ipc_from_reader};
+    /// use serde::{Serialize, Deserialize};
+    /// use std::io::Cursor;
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct D {
+    ///     name: String,
+    ///     age: u32
+    /// }
+    ///
+    /// let df = DataFrame::new(vec![D { name: "Daniel".to_string(), age: 26 }]);
+    ///
+    /// let mut buffer = Vec::new();
+    /// df.write_ipc(&mut buffer).unwrap();
+    ///
+    /// let new_df = read_ipc_from_reader::<D, _>(Cursor::new(buffer)).unwrap();
+    /// assert_eq!(new_df.len(), 1);
+    /// ```
+    pub fn write_ipc<W: Write>(&self, writer: W) -> Result<(), errors::Error> {
+        self.write_ipc_with(writer, &IpcWriteOptions::default())
+    }
+
+    /// Write a DataFrame as Arrow IPC to any writer using custom options.
+    pub fn write_ipc_with<W: Write>(
+        &self,
+        writer: W,
+        options: &IpcWriteOptions,
+    ) -> Result<(), errors::Error> {
+        write_ipc_rows(writer, &self.data, options)
+    }
+
+    /// Save a DataFrame as a Hive-style partitioned Parquet dataset, with one directory per partition key:
+    /// `root/column=value/.../part-N.parquet`. The key must be a struct of scalar fields; its fields that are
+    /// also fields of the DataFrame are only stored in the directory names.
+    /// Null values are written as `column=__HIVE_DEFAULT_PARTITION__` and empty strings as `column=`.
+    /// New files are added next to the existing ones, so a dataset can be written in several calls.
+    /// Use [crate::read_parquet_partitioned] to read it back.
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, read_parquet_partitioned};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct Sale {
+    ///     year: i32,
+    ///     amount: f64
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Key {
+    ///     year: i32
+    /// }
+    ///
+    /// let df = DataFrame::new(vec![Sale { year: 2022, amount: 1.5 }, Sale { year: 2023, amount: 2.0 }]);
+    /// df.to_parquet_partitioned("tmp/sales".to_string(), |s| Key { year: s.year }).unwrap();
+    ///
+    /// let sales = read_parquet_partitioned::<Sale>("tmp/sales".to_string()).unwrap();
+    /// # std::fs::remove_dir_all("tmp/sales").unwrap();
+    /// ```
+    pub fn to_parquet_partitioned<K: Serialize, F: Fn(&D) -> K>(
+        &self,
+        root: String,
+        key: F,
+    ) -> Result<(), errors::Error> {
+        self.to_parquet_partitioned_with(root, key, &ParquetWriteOptions::default())
+    }
+
+    /// Save a DataFrame as a Hive-style partitioned Parquet dataset using custom options.
+    pub fn to_parquet_partitioned_with<K: Serialize, F: Fn(&D) -> K>(
+        &self,
+        root: String,
+        key: F,
+        options: &ParquetWriteOptions,
+    ) -> Result<(), errors::Error> {
+        log::debug!("Saving DataFrame to partitioned Parquet at path: {}", root);
+
+        // The schema is traced once, so every partition is written with the same types.
+        let fields = parquet_fields(&self.data, options, true)?;
+
+        // Rows are grouped by partition in a single pass, keeping the order of the first appearance.
+        let mut columns = Vec::new();
+        let mut indexes: HashMap<PathBuf, usize> = HashMap::new();
+        let mut partitions: Vec<(PathBuf, Vec<&D>)> = Vec::new();
+        for row in self.data.iter() {
+            let partition = partition_values(&key(row))?;
+            let dir = partition_dir(&partition);
+            if columns.is_empty() {
+                columns = partition.into_iter().map(|(c, _)| c).collect();
+            }
+
+            match indexes.get(&dir) {
+                Some(i) => partitions[*i].1.push(row),
+                None => {
+                    indexes.insert(dir.clone(), partitions.len());
+                    partitions.push((dir, vec![row]));
+                }
+            }
+        }
+
+        for (dir, rows) in partitions {
+            let dir = Path::new(&root).join(dir);
+            if let Err(e) = fs::create_dir_all(&dir) {
                 return Err(errors::Error {
                     message: e.to_string(),
-                })
+                });
             }
-        };
 
-        for row in self.data[0..self.len()].into_iter() {
-            match writer.serialize(row) {
-                Ok(_) => (),
+            let mut n = 0;
+            while dir.join(format!("part-{:05}.parquet", n)).exists() {
+                n += 1;
+            }
+
+            let path = dir.join(format!("part-{:05}.parquet", n));
+            match File::create(&path) {
+                Ok(file) => {
+                    // A truncated file would make the whole dataset unreadable.
+                    if let Err(e) = write_parquet_rows(file, &rows, &fields, options, &columns) {
+                        let _ = fs::remove_file(&path);
+                        return Err(e);
+                    }
+                }
                 Err(e) => {
                     return Err(errors::Error {
                         message: e.to_string(),
                     })
                 }
-            };
+            }
         }
 
-        match writer.flush() {
-            Ok(_) => Ok(()),
-            Err(e) => Err(errors::Error {
-                message: e.to_string(),
-            }),
+        Ok(())
+    }
+}
+
+/// Traces the arrow fields of the rows, null only fields are allowed for partitioned datasets.
+fn parquet_fields<T: Serialize>(
+    rows: &[T],
+    options: &ParquetWriteOptions,
+    allow_null_fields: bool,
+) -> Result<Vec<Field>, errors::Error> {
+    let tracing = TracingOptions::default()
+        .string_dictionary_encoding(options.dictionary_encoding)
+        .allow_null_fields(allow_null_fields);
+
+    match serialize_into_fields(rows, tracing) {
+        Ok(fields) => Ok(fields),
+        Err(e) => Err(errors::Error {
+            message: e.to_string(),
+        }),
+    }
+}
+
+/// Write the rows as Parquet with the traced fields, leaving out the columns in `skip` (e.g. partition columns).
+fn write_parquet_rows<T: Serialize, W: Write>(
+    writer: W,
+    rows: &[T],
+    fields: &[Field],
+    options: &ParquetWriteOptions,
+    skip: &[String],
+) -> Result<(), errors::Error> {
+    let write_options = options.write_options()?;
+    let mut schema = fields.to_vec();
+
+    // Fields marked by their serializer, found in a single pass that stops once every field had a value.
+    let mut marked: Vec<(&'static str, &'static str)> = Vec::new();
+    marked_fields(rows, |field, marker| {
+        if !marked.iter().any(|(f, _)| *f == field) {
+            marked.push((field, marker));
+        }
+    });
+
+    // Decimal fields are written as DECIMAL, unless the options set the precision and scale of the column.
+    // The precision and scale of the other ones are the smallest that fit all their values.
+    let mut columns: Vec<(String, Option<(usize, usize)>)> = options
+        .decimals
+        .iter()
+        .map(|(column, precision, scale)| (column.clone(), Some((*precision, *scale))))
+        .collect();
+    for (field, marker) in marked.iter() {
+        if *marker == DECIMAL_NAME && !columns.iter().any(|(name, _)| name == field) {
+            columns.push((field.to_string(), None));
         }
     }
 
-    /// Save a DataFrame as a Parquet file.
-    pub fn to_parquet(&self, path: String) -> Result<(), errors::Error> {
-        log::debug!("Saving DataFrame to Parquet file at path: {}", path);
+    let mut decimals = Vec::new();
+    for (column, fit) in columns {
+        if let Some((precision, scale)) = fit {
+            check_decimal(&column, precision, scale)?;
+        }
 
-        let schema = match serialize_into_fields(&self.data, TracingOptions::default()) {
-            Ok(s) => s,
-            Err(e) => {
+        match schema.iter().position(|f| f.name == column) {
+            Some(i) => {
+                // Dictionary encoded strings are serialized as plain strings before the conversion.
+                if let DataType::Dictionary(_, _, _) = schema[i].data_type {
+                    schema[i].data_type = DataType::LargeUtf8;
+                }
+                decimals.push((i, fit));
+            }
+            None => {
                 return Err(errors::Error {
-                    message: e.to_string(),
+                    message: format!("Column {} not found!", column),
                 })
             }
+        }
+    }
+
+    // Values of the time crate are written as DATE and TIMESTAMP columns.
+    let mut temporals: Vec<(usize, Temporal)> = Vec::new();
+    for (field, marker) in marked {
+        let i = match schema.iter().position(|f| f.name == field) {
+            Some(i) => i,
+            None => continue,
         };
+        if let Some(temporal) = Temporal::from_marker(marker) {
+            if !decimals.iter().any(|(d, _)| *d == i) {
+                temporals.push((i, temporal));
+            }
+        }
+    }
 
-        let arrays = match serialize_into_arrays(&schema, &self.data) {
-            Ok(a) => a,
+    let skipped: Vec<bool> = schema.iter().map(|f| skip.contains(&f.name)).collect();
+
+    let row_group_size = match options.row_group_size {
+        Some(size) if size > 0 => size,
+        Some(_) => {
+            return Err(errors::Error {
+                message: "Row group size must be greater than zero!".to_string(),
+            })
+        }
+        None => std::cmp::max(rows.len(), 1),
+    };
+
+    let mut groups = Vec::new();
+    for chunk in rows.chunks(row_group_size) {
+        let mut arrays = match serialize_into_arrays(&schema, chunk) {
+            Ok(arrays) => arrays,
             Err(e) => {
                 return Err(errors::Error {
                     message: e.to_string(),
@@ -263,41 +900,279 @@ impl<D: Clone + DeserializeOwned + Serialize> DataFrame<D> {
             }
         };
 
-        match write_chunk_parquet(&path, Schema::from(schema), Chunk::new(arrays)) {
-            Ok(_) => Ok(()),
-            Err(_) => Err(errors::Error {
-                message: "Error saving parquet!".to_string(),
-            }),
+        for (i, temporal) in temporals.iter() {
+            arrays[*i] = temporal_array(arrays[*i].as_ref(), *temporal)?;
+        }
+        groups.push(arrays);
+    }
+
+    for (i, fit) in decimals {
+        // Columns without precision and scale hold Decimal strings, the scale only rounds floats.
+        let mut values = Vec::with_capacity(groups.len());
+        for arrays in groups.iter() {
+            values.push(decimal_values(
+                arrays[i].as_ref(),
+                fit.map_or(0, |(_, scale)| scale),
+            )?);
+        }
+
+        let (precision, scale) = match fit {
+            Some(fit) => fit,
+            None => {
+                let (precision, scale) = decimal_fit(values.iter().flatten().flatten());
+                check_decimal(&schema[i].name, precision, scale)?;
+                (precision, scale)
+            }
+        };
+        for (arrays, values) in groups.iter_mut().zip(values) {
+            arrays[i] = unscaled_array(values, precision, scale)?;
+        }
+        schema[i].data_type = DataType::Decimal(precision, scale);
+    }
+
+    let chunks = groups
+        .into_iter()
+        .map(|arrays| {
+            Ok(Chunk::new(
+                arrays
+                    .into_iter()
+                    .zip(skipped.iter())
+                    .filter(|(_, skipped)| !**skipped)
+                    .map(|(array, _)| array)
+                    .collect(),
+            ))
+        })
+        .collect();
+
+    for (i, temporal) in temporals {
+        schema[i].data_type = temporal.data_type();
+    }
+    let schema: Vec<Field> = schema
+        .into_iter()
+        .zip(skipped.iter())
+        .filter(|(_, skipped)| !**skipped)
+        .map(|(field, _)| field)
+        .collect();
+
+    match write_chunks_parquet(writer, Schema::from(schema), chunks, write_options) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(errors::Error {
+            message: "Error saving parquet!".to_string(),
+        }),
+    }
+}
+
+fn check_decimal(column: &str, precision: usize, scale: usize) -> Result<(), errors::Error> {
+    if precision == 0 || precision > 38 || scale > precision {
+        return Err(errors::Error {
+            message: format!(
+                "Invalid DECIMAL({}, {}) for column {}!",
+                precision, scale, column
+            ),
+        });
+    }
+    Ok(())
+}
+
+fn create_file(path: &str) -> Result<BufWriter<File>, errors::Error> {
+    match File::create(path) {
+        Ok(file) => Ok(BufWriter::new(file)),
+        Err(e) => Err(errors::Error {
+            message: e.to_string(),
+        }),
+    }
+}
+
+fn write_json_rows<D: Serialize, W: Write>(mut writer: W, rows: &[D]) -> Result<(), errors::Error> {
+    let to_error = |e: std::io::Error| errors::Error {
+        message: e.to_string(),
+    };
+
+    writer.write_all(b"[").map_err(to_error)?;
+    for (i, row) in rows.iter().enumerate() {
+        let separator: &[u8] = if i == 0 { b"\n" } else { b",\n" };
+        writer.write_all(separator).map_err(to_error)?;
+        if let Err(e) = serde_json::to_writer(&mut writer, row) {
+            return Err(errors::Error {
+                message: e.to_string(),
+            });
         }
     }
+    let end: &[u8] = if rows.is_empty() { b"]\n" } else { b"\n]\n" };
+    writer.write_all(end).map_err(to_error)?;
+    writer.flush().map_err(to_error)
 }
 
-fn write_chunk_parquet(
+fn write_ndjson_rows<D: Serialize, W: Write>(
+    mut writer: W,
+    rows: &[D],
+) -> Result<(), errors::Error> {
+    let to_error = |e: std::io::Error| errors::Error {
+        message: e.to_string(),
+    };
+
+    for row in rows.iter() {
+        if let Err(e) = serde_json::to_writer(&mut writer, row) {
+            return Err(errors::Error {
+                message: e.to_string(),
+            });
+        }
+        writer.write_all(b"\n").map_err(to_error)?;
+    }
+    writer.flush().map_err(to_error)
+}
+
+fn write_csv_file<D: Serialize>(
     path: &str,
-    schema: Schema,
-    chunk: Chunk<Box<dyn Array>>,
-) -> arrow2::error::Result<()> {
-    let options = WriteOptions {
-        write_statistics: false,
-        compression: CompressionOptions::Uncompressed,
-        version: Version::V2,
-        data_pagesize_limit: None,
+    rows: &[D],
+    options: &CsvWriteOptions,
+) -> Result<(), errors::Error> {
+    let file = if options.append {
+        OpenOptions::new().create(true).append(true).open(path)
+    } else {
+        File::create(path)
+    };
+
+    let file = match file {
+        Ok(f) => f,
+        Err(e) => {
+            return Err(errors::Error {
+                message: e.to_string(),
+            })
+        }
     };
 
-    let iter = vec![Ok(chunk)];
+    let mut builder = options.writer_builder();
+    if options.append {
+        // Do not repeat the header when appending to a file with rows already.
+        let empty = match file.metadata() {
+            Ok(metadata) => metadata.len() == 0,
+            Err(_) => true,
+        };
+        builder.has_headers(options.has_headers && empty);
+    }
+
+    write_csv_rows(file, rows, builder, options)
+}
+
+fn write_csv_rows<D: Serialize, W: Write>(
+    writer: W,
+    rows: &[D],
+    builder: csv::WriterBuilder,
+    options: &CsvWriteOptions,
+) -> Result<(), errors::Error> {
+    let mut writer = builder.from_writer(writer);
+
+    for row in rows.iter() {
+        match writer.serialize(Formatted::new(row, options)) {
+            Ok(_) => (),
+            Err(e) => {
+                return Err(errors::Error {
+                    message: e.to_string(),
+                })
+            }
+        };
+    }
+
+    match writer.flush() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(errors::Error {
+            message: e.to_string(),
+        }),
+    }
+}
+
+/// Write the rows as Arrow IPC record batches, in the layout of the given options.
+fn write_ipc_rows<T: Serialize + DeserializeOwned, W: Write>(
+    writer: W,
+    rows: &[T],
+    options: &IpcWriteOptions,
+) -> Result<(), errors::Error> {
+    let to_error = |e: arrow2::error::Error| errors::Error {
+        message: e.to_string(),
+    };
+    // The serde_arrow messages are used without their backtraces.
+    let to_serde_error = |e: serde_arrow::Error| errors::Error {
+        message: e.message().to_string(),
+    };
+
+    let batch_size = match options.batch_size {
+        Some(size) if size > 0 => size,
+        Some(_) => {
+            return Err(errors::Error {
+                message: "Batch size must be greater than zero!".to_string(),
+            })
+        }
+        None => std::cmp::max(rows.len(), 1),
+    };
+
+    // Without rows the schema is traced from a row of default values, so an empty file is still valid.
+    let tracing = TracingOptions::default().allow_null_fields(true);
+    let fields = if rows.is_empty() {
+        serialize_into_fields(&[sample::<T>()?], tracing)
+    } else {
+        serialize_into_fields(rows, tracing)
+    }
+    .map_err(to_serde_error)?;
+    let schema = Schema::from(fields.clone());
+
+    let mut chunks = Vec::new();
+    for batch in rows.chunks(batch_size) {
+        let arrays = serialize_into_arrays(&fields, batch).map_err(to_serde_error)?;
+        chunks.push(Chunk::new(arrays));
+    }
+
+    let mut writer = match options.format {
+        IpcFormat::File => {
+            let mut writer =
+                ipc::write::FileWriter::try_new(writer, schema, None, options.write_options())
+                    .map_err(to_error)?;
+            for chunk in chunks.iter() {
+                writer.write(chunk, None).map_err(to_error)?;
+            }
+            writer.finish().map_err(to_error)?;
+            writer.into_inner()
+        }
+        IpcFormat::Stream => {
+            let mut writer = ipc::write::StreamWriter::new(writer, options.write_options());
+            writer.start(&schema, None).map_err(to_error)?;
+            for chunk in chunks.iter() {
+                writer.write(chunk, None).map_err(to_error)?;
+            }
+            writer.finish().map_err(to_error)?;
+            writer.into_inner()
+        }
+    };
+
+    // Buffered writers only report the errors of the last writes when flushed.
+    match writer.flush() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(errors::Error {
+            message: e.to_string(),
+        }),
+    }
+}
 
+fn write_chunks_parquet<W: Write>(
+    writer: W,
+    schema: Schema,
+    chunks: Vec<arrow2::error::Result<Chunk<Box<dyn Array>>>>,
+    options: WriteOptions,
+) -> arrow2::error::Result<()> {
     let encodings = schema
         .fields
         .iter()
-        .map(|f| transverse(&f.data_type, |_| Encoding::Plain))
+        .map(|f| {
+            transverse(&f.data_type, |data_type| match data_type {
+                DataType::Dictionary(_, _, _) => Encoding::RleDictionary,
+                _ => Encoding::Plain,
+            })
+        })
         .collect();
 
-    let row_groups = RowGroupIterator::try_new(iter.into_iter(), &schema, options, encodings)?;
-
-    // Create a new empty file
-    let file = File::create(path)?;
+    let row_groups = RowGroupIterator::try_new(chunks.into_iter(), &schema, options, encodings)?;
 
-    let mut writer = FileWriter::try_new(file, schema, options)?;
+    let mut writer = FileWriter::try_new(writer, schema, options)?;
 
     for group in row_groups {
         writer.write(group?)?;
@@ -387,38 +1262,88 @@ impl<'a, D: Clone + DeserializeOwned + Serialize> SliceDataFrame<'a, D> {
 
     /// Save a SliceDataFrame to a CSV file.
     pub fn to_csv(&self, path: String) -> Result<(), errors::Error> {
-        let mut writer = match csv::Writer::from_path(path) {
-            Ok(w) => w,
-            Err(e) => {
-                return Err(errors::Error {
-                    message: e.to_string(),
-                })
-            }
-        };
-
-        for row in self.dataframe.data[self.start..self.end].into_iter() {
-            match writer.serialize(row) {
-                Ok(_) => (),
-                Err(e) => {
-                    return Err(errors::Error {
-                        message: e.to_string(),
-                    })
-                }
-            };
-        }
+        self.to_csv_with(path, &CsvWriteOptions::default())
+    }
 
-        match writer.flush() {
-            Ok(_) => Ok(()),
-            Err(e) => Err(errors::Error {
-                message: e.to_string(),
-            }),
-        }
+    /// Save a SliceDataFrame to a CSV file using custom options.
+    pub fn to_csv_with(
+        &self,
+        path: String,
+        options: &CsvWriteOptions,
+    ) -> Result<(), errors::Error> {
+        log::debug!("Saving SliceDataFrame to CSV file at path: {}", path);
+        write_csv_file(&path, &self.dataframe.data[self.start..self.end], options)
     }
 
     /// Save a SliceDataFrame as a Parquet file.
     pub fn to_parquet(&self, path: String) -> Result<(), errors::Error> {
         self.clone().to_parquet(path)
     }
+
+    /// Save a SliceDataFrame as a Parquet file using custom options.
+    pub fn to_parquet_with(
+        &self,
+        path: String,
+        options: &ParquetWriteOptions,
+    ) -> Result<(), errors::Error> {
+        self.clone().to_parquet_with(path, options)
+    }
+
+    /// Save a SliceDataFrame as an Arrow IPC file, see [DataFrame::to_ipc].
+    pub fn to_ipc(&self, path: String) -> Result<(), errors::Error> {
+        self.to_ipc_with(path, &IpcWriteOptions::default())
+    }
+
+    /// Save a SliceDataFrame as Arrow IPC using custom options, see [DataFrame::to_ipc_with].
+    pub fn to_ipc_with(
+        &self,
+        path: String,
+        options: &IpcWriteOptions,
+    ) -> Result<(), errors::Error> {
+        log::debug!("Saving SliceDataFrame to Arrow IPC file at path: {}", path);
+        write_ipc_rows(
+            create_file(&path)?,
+            &self.dataframe.data[self.start..self.end],
+            options,
+        )
+    }
+
+    /// Write a SliceDataFrame as CSV to any writer, see [DataFrame::write_csv].
+    pub fn write_csv<W: Write>(
+        &self,
+        writer: W,
+        options: &CsvWriteOptions,
+    ) -> Result<(), errors::Error> {
+        write_csv_rows(
+            writer,
+            &self.dataframe.data[self.start..self.end],
+            options.writer_builder(),
+            options,
+        )
+    }
+
+    /// Save a SliceDataFrame as a JSON file, see [DataFrame::to_json].
+    pub fn to_json(&self, path: String) -> Result<(), errors::Error> {
+        log::debug!("Saving SliceDataFrame to JSON file at path: {}", path);
+        write_json_rows(
+            create_file(&path)?,
+            &self.dataframe.data[self.start..self.end],
+        )
+    }
+
+    /// Save a SliceDataFrame as a newline delimited JSON file, see [DataFrame::to_ndjson].
+    pub fn to_ndjson(&self, path: String) -> Result<(), errors::Error> {
+        log::debug!("Saving SliceDataFrame to NDJSON file at path: {}", path);
+        write_ndjson_rows(
+            create_file(&path)?,
+            &self.dataframe.data[self.start..self.end],
+        )
+    }
+
+    /// Write a SliceDataFrame as Parquet to any writer, see [DataFrame::write_parquet].
+    pub fn write_parquet<W: Write>(&self, writer: W) -> Result<(), errors::Error> {
+        self.clone().write_parquet(writer)
+    }
 }
 
 impl<'a, D: Clone + DeserializeOwned + Serialize + Debug> fmt::Debug for SliceDataFrame<'a, D> {
@@ -434,6 +1359,23 @@ impl<'a, D: Clone + DeserializeOwned + Serialize + Debug> fmt::Debug for SliceDa
     }
 }
 
+impl<'a, D: Clone + DeserializeOwned + Serialize> Group<'a, D> {
+    /// Iterates over the rows of the group, in the original order of the rows.
+    pub fn iter(&self) -> impl Iterator<Item = &'a D> {
+        self.data.clone().copied()
+    }
+
+    /// Number of rows of the group.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the group has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.data.len() == 0
+    }
+}
+
 impl<'a, D: Clone + DeserializeOwned + Serialize, I: Eq + Clone + Hash, F>
     GroupedDataFrame<'a, D, I, F>
 where
@@ -443,32 +1385,211 @@ where
         GroupedDataFrame {
             dataframe: df,
             index: index,
+            groups: OnceCell::new(),
         }
     }
 
+    /// Iterates over the groups and their index, in order of first appearance.
+    /// Example:
+    /// ```
+    /// use combee::dataframe::DataFrame;
+    ///
+    /// let df = DataFrame::new(vec![('a', 1), ('b', 2), ('a', 3)]);
+    /// let grouped = df.groupby(|r| r.0);
+    ///
+    /// for (index, group) in grouped.groups() {
+    ///     let values: Vec<i32> = group.iter().map(|r| r.1).collect();
+    ///     println!("{}: {:?} ({} rows)", index, values, group.len());
+    /// }
+    /// assert_eq!(grouped.ngroups(), 2);
+    /// ```
+    pub fn groups(&self) -> impl Iterator<Item = (I, Group<'_, D>)> + '_ {
+        self.group_index()
+            .groups
+            .iter()
+            .map(|(ind, group)| (ind.clone(), Group { data: group.iter() }))
+    }
+
+    /// Rows of the group with the given index as a new DataFrame, None if there is no such group.
+    /// Example:
+    /// ```
+    /// use combee::dataframe::DataFrame;
+    ///
+    /// let df = DataFrame::new(vec![('a', 1), ('b', 2), ('a', 3)]);
+    /// let grouped = df.groupby(|r| r.0);
+    ///
+    /// assert_eq!(grouped.get_group(&'a').unwrap().take(2), vec![('a', 1), ('a', 3)]);
+    /// assert!(grouped.get_group(&'z').is_none());
+    /// ```
+    pub fn get_group(&self, index: &I) -> Option<DataFrame<D>> {
+        let groups = self.group_index();
+        let position = groups.positions.get(index)?;
+        Some(DataFrame::new(
+            groups.groups[*position]
+                .1
+                .iter()
+                .map(|row| (*row).clone())
+                .collect(),
+        ))
+    }
+
+    /// Number of groups.
+    pub fn ngroups(&self) -> usize {
+        self.group_index().groups.len()
+    }
+
     /// Aggregates a GroupedDataFrame in a new DataFrame using a aggregator function.
+    /// The groups are in the order of the first row of each group in the DataFrame.
     pub fn agg<S: Clone + DeserializeOwned + Serialize, G>(&self, aggregator: G) -> DataFrame<S>
     where
         G: Fn(&I, &Group<D>) -> S,
     {
-        let mut hashmap: HashMap<I, Vec<&'a D>> = HashMap::new();
+        let data = self
+            .group_index()
+            .groups
+            .iter()
+            .map(|(ind, group)| aggregator(ind, &Group { data: group.iter() }))
+            .collect();
+        DataFrame::new(data)
+    }
 
-        for row in self.dataframe.data.iter() {
-            let ind = (self.index)(&row);
-            match hashmap.get_mut(&ind) {
-                Some(group) => group.push(row),
-                None => {
-                    let mut v = Vec::new();
-                    v.push(row);
-                    hashmap.insert(ind, v);
-                }
-            }
-        }
+    /// Aggregates a GroupedDataFrame like [GroupedDataFrame::agg], with the groups sorted by their index.
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, functions::sum};
+    ///
+    /// let df = DataFrame::new(vec![(3, 1.0), (1, 2.0), (3, 0.5), (2, 4.0)]);
+    ///
+    /// let totals = df.groupby(|r| r.0).agg_sorted(|index, g| (*index, sum(g, |r| r.1)));
+    /// assert_eq!(totals.take(3), vec![(1, 2.0), (2, 4.0), (3, 1.5)]);
+    /// ```
+    pub fn agg_sorted<S: Clone + DeserializeOwned + Serialize, G>(
+        &self,
+        aggregator: G,
+    ) -> DataFrame<S>
+    where
+        I: Ord,
+        G: Fn(&I, &Group<D>) -> S,
+    {
+        let mut groups: Vec<&(I, Vec<&D>)> = self.group_index().groups.iter().collect();
+        groups.sort_by(|a, b| a.0.cmp(&b.0));
 
-        let data = hashmap
+        let data = groups
             .iter()
             .map(|(ind, group)| aggregator(ind, &Group { data: group.iter() }))
             .collect();
         DataFrame::new(data)
     }
+
+    /// Computes a value for each row of the DataFrame using its group, in the original order of the rows.
+    /// The function is called once per group and returns the function applied to each row of the group,
+    /// so aggregates of the group (e.g. [crate::functions::sum] or [crate::functions::avg]) are computed once.
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, functions::sum};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct Sale {
+    ///     store: String,
+    ///     amount: f64
+    /// }
+    ///
+    /// let df = DataFrame::new(vec![
+    ///     Sale {store: "a".to_string(), amount: 1.0},
+    ///     Sale {store: "b".to_string(), amount: 5.0},
+    ///     Sale {store: "a".to_string(), amount: 3.0}
+    /// ]);
+    ///
+    /// let shares = df.groupby(|s| s.store.clone()).transform(|_, g| {
+    ///     let total = sum(g, |s| s.amount);
+    ///     move |s: &Sale| s.amount / total
+    /// });
+    /// assert_eq!(shares.take(3), vec![0.25, 1.0, 0.75]);
+    /// ```
+    pub fn transform<S: Clone + DeserializeOwned + Serialize, G, R>(&self, func: G) -> DataFrame<S>
+    where
+        G: Fn(&I, &Group<D>) -> R,
+        R: Fn(&D) -> S,
+    {
+        let groups = self.group_index();
+
+        let functions: Vec<R> = groups
+            .groups
+            .iter()
+            .map(|(ind, group)| func(ind, &Group { data: group.iter() }))
+            .collect();
+
+        let data = self
+            .dataframe
+            .data
+            .iter()
+            .zip(groups.row_groups.iter())
+            .map(|(row, position)| functions[*position](row))
+            .collect();
+        DataFrame::new(data)
+    }
+
+    /// Keeps the rows of the groups that satisfy the condition, in the original order of the rows (like HAVING in SQL).
+    /// Example:
+    /// ```
+    /// use combee::{dataframe::DataFrame, functions::count};
+    ///
+    /// let orders = DataFrame::new(vec![('a', 1.0), ('b', 2.0), ('a', 3.0), ('c', 4.0), ('a', 5.0)]);
+    ///
+    /// let frequent = orders.groupby(|o| o.0).filter_groups(|_, g| count(g) >= 2);
+    /// assert_eq!(frequent.take(3), vec![('a', 1.0), ('a', 3.0), ('a', 5.0)]);
+    /// ```
+    pub fn filter_groups<G>(&self, condition: G) -> DataFrame<D>
+    where
+        G: Fn(&I, &Group<D>) -> bool,
+    {
+        let groups = self.group_index();
+
+        let keep: Vec<bool> = groups
+            .groups
+            .iter()
+            .map(|(ind, group)| condition(ind, &Group { data: group.iter() }))
+            .collect();
+
+        let data = self
+            .dataframe
+            .data
+            .iter()
+            .zip(groups.row_groups.iter())
+            .filter(|(_, position)| keep[**position])
+            .map(|(row, _)| row.clone())
+            .collect();
+        DataFrame::new(data)
+    }
+
+    /// Groups of the DataFrame, computed on first use.
+    fn group_index(&self) -> &GroupIndex<'a, D, I> {
+        self.groups.get_or_init(|| {
+            let mut positions: HashMap<I, usize> = HashMap::new();
+            let mut groups: Vec<(I, Vec<&'a D>)> = Vec::new();
+            let mut row_groups = Vec::with_capacity(self.dataframe.len());
+
+            for row in self.dataframe.data.iter() {
+                let ind = (self.index)(row);
+                match positions.get(&ind) {
+                    Some(position) => {
+                        groups[*position].1.push(row);
+                        row_groups.push(*position);
+                    }
+                    None => {
+                        positions.insert(ind.clone(), groups.len());
+                        row_groups.push(groups.len());
+                        groups.push((ind, vec![row]));
+                    }
+                }
+            }
+
+            GroupIndex {
+                groups,
+                positions,
+                row_groups,
+            }
+        })
+    }
 }
@@ -0,0 +1,411 @@
+use arrow2::{
+    array::{Array, PrimitiveArray, Utf8Array},
+    datatypes::DataType,
+};
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+use crate::errors;
+
+/// Name of the newtype a Decimal is serialized as, so the parquet writer finds the decimal columns.
+pub(crate) const DECIMAL_NAME: &str = "combee::Decimal";
+
+/// Fixed point number stored as an unscaled integer and a scale, value = unscaled / 10^scale.
+/// It is serialized as a string, so it keeps the precision of parquet DECIMAL columns.
+/// Decimals are compared by value, e.g. 12.5 == 12.50.
+/// Example:
+/// ```
+/// use combee::decimal::Decimal;
+///
+/// let price: Decimal = "-12.50".parse().unwrap();
+///
+/// assert_eq!(price, Decimal::new(-1250, 2));
+/// assert_eq!(price, Decimal::new(-125, 1));
+/// assert_eq!(price.to_string(), "-12.50");
+/// assert_eq!(price.to_f64(), -12.5);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Decimal {
+    unscaled: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    /// Instantiate a new Decimal from the unscaled value and the number of decimal places.
+    pub fn new(unscaled: i128, scale: u32) -> Self {
+        Decimal { unscaled, scale }
+    }
+
+    /// Unscaled integer value.
+    pub fn unscaled(&self) -> i128 {
+        self.unscaled
+    }
+
+    /// Number of decimal places.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Closest f64 to the decimal value.
+    pub fn to_f64(&self) -> f64 {
+        self.unscaled as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    /// Returns the decimal with another scale, rounding half away from zero when decimal places are removed.
+    /// Returns None on overflow.
+    pub fn rescale(&self, scale: u32) -> Option<Decimal> {
+        if scale >= self.scale {
+            let factor = 10i128.checked_pow(scale - self.scale)?;
+            let unscaled = self.unscaled.checked_mul(factor)?;
+            return Some(Decimal::new(unscaled, scale));
+        }
+
+        let factor = match 10i128.checked_pow(self.scale - scale) {
+            Some(f) => f,
+            None => return Some(Decimal::new(0, scale)),
+        };
+        let quotient = self.unscaled / factor;
+        let remainder = self.unscaled % factor;
+        // Same as remainder * 2 >= factor, without overflowing for large factors.
+        let unscaled = if remainder.abs() >= factor - remainder.abs() {
+            quotient + self.unscaled.signum()
+        } else {
+            quotient
+        };
+        Some(Decimal::new(unscaled, scale))
+    }
+
+    /// Number of significant digits of the unscaled value.
+    fn digits(&self) -> u32 {
+        let mut value = self.unscaled.unsigned_abs();
+        let mut digits = 1;
+        while value >= 10 {
+            value /= 10;
+            digits += 1;
+        }
+        digits
+    }
+
+    /// Same value with the trailing zeros of the decimal places removed, e.g. 12.50 -> 12.5.
+    fn normalize(&self) -> Decimal {
+        let mut decimal = *self;
+        while decimal.scale > 0 && decimal.unscaled % 10 == 0 {
+            decimal.unscaled /= 10;
+            decimal.scale -= 1;
+        }
+        decimal
+    }
+
+    /// Compares the decimal values, whatever their scales.
+    pub(crate) fn compare(&self, other: &Decimal) -> Ordering {
+        let sign = self.unscaled.signum().cmp(&other.unscaled.signum());
+        if sign != Ordering::Equal || self.unscaled == 0 {
+            return sign;
+        }
+
+        let scale = self.scale.max(other.scale);
+        match (self.rescale(scale), other.rescale(scale)) {
+            (Some(a), Some(b)) => a.unscaled.cmp(&b.unscaled),
+            // Both values have the same sign, so the larger magnitude is the larger positive value.
+            _ if self.unscaled > 0 => self.compare_magnitude(other),
+            _ => other.compare_magnitude(self),
+        }
+    }
+
+    /// Compares the absolute values digit by digit, for scales too far apart to be rescaled.
+    fn compare_magnitude(&self, other: &Decimal) -> Ordering {
+        let (integer, fraction) = self.digits_parts();
+        let (other_integer, other_fraction) = other.digits_parts();
+        integer
+            .len()
+            .cmp(&other_integer.len())
+            .then_with(|| integer.cmp(&other_integer))
+            .then_with(|| fraction.cmp(&other_fraction))
+    }
+
+    /// Integer digits without leading zeros and decimal places without trailing zeros of the absolute value.
+    fn digits_parts(&self) -> (String, String) {
+        let scale = self.scale as usize;
+        let digits = format!(
+            "{:0>width$}",
+            self.unscaled.unsigned_abs(),
+            width = scale + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        (
+            integer.trim_start_matches('0').to_string(),
+            fraction.trim_end_matches('0').to_string(),
+        )
+    }
+
+    pub(crate) fn from_parquet(
+        decimal: &parquet::data_type::Decimal,
+    ) -> Result<Decimal, errors::Error> {
+        Decimal::from_be_bytes(
+            decimal.data(),
+            decimal.precision(),
+            parquet_scale(decimal.scale())?,
+        )
+    }
+
+    /// Decimal stored as a big endian two's complement unscaled value, as in parquet byte array columns.
+    pub(crate) fn from_be_bytes(
+        data: &[u8],
+        precision: i32,
+        scale: u32,
+    ) -> Result<Decimal, errors::Error> {
+        if data.len() > 16 {
+            return Err(errors::Error {
+                message: format!(
+                    "Decimal with precision {} does not fit in 128 bits!",
+                    precision
+                ),
+            });
+        }
+
+        // Sign extended to 128 bits.
+        let fill = match data.first() {
+            Some(b) if *b & 0x80 != 0 => 0xff,
+            _ => 0x00,
+        };
+        let mut bytes = [fill; 16];
+        bytes[16 - data.len()..].copy_from_slice(data);
+
+        Ok(Decimal::new(i128::from_be_bytes(bytes), scale))
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let decimal = self.normalize();
+        decimal.unscaled.hash(state);
+        decimal.scale.hash(state);
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.unscaled.unsigned_abs().to_string();
+        let sign = if self.unscaled < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = errors::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || errors::Error {
+            message: format!("Invalid decimal '{}'!", s),
+        };
+
+        let (negative, unsigned) = match s.trim().strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.trim().strip_prefix('+').unwrap_or(s.trim())),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((i, f)) => (i, f),
+            None => (unsigned, ""),
+        };
+
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+        if !integer
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let mut unscaled: i128 = 0;
+        for c in integer.chars().chain(fraction.chars()) {
+            unscaled = unscaled
+                .checked_mul(10)
+                .and_then(|u| u.checked_add(c.to_digit(10).unwrap() as i128))
+                .ok_or_else(invalid)?;
+        }
+
+        Ok(Decimal::new(
+            if negative { -unscaled } else { unscaled },
+            fraction.len() as u32,
+        ))
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(DECIMAL_NAME, &self.to_string())
+    }
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal number")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Decimal, E> {
+        v.parse().map_err(|e: errors::Error| E::custom(e.message))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Decimal, E> {
+        Ok(Decimal::new(v as i128, 0))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Decimal, E> {
+        Ok(Decimal::new(v as i128, 0))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Decimal, E> {
+        self.visit_str(&v.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+}
+
+/// Scale of a parquet DECIMAL, negative scales are not supported.
+pub(crate) fn parquet_scale(scale: i32) -> Result<u32, errors::Error> {
+    u32::try_from(scale).map_err(|_| errors::Error {
+        message: format!("DECIMAL with negative scale {} is not supported!", scale),
+    })
+}
+
+/// Smallest DECIMAL(precision, scale) that fits all the values.
+pub(crate) fn decimal_fit<'a, I: Iterator<Item = &'a Decimal>>(values: I) -> (usize, usize) {
+    let (integer, scale) = values.fold((0, 0), |(integer, scale), d| {
+        (
+            integer.max(d.digits().saturating_sub(d.scale)),
+            scale.max(d.scale),
+        )
+    });
+    ((integer + scale).max(1) as usize, scale as usize)
+}
+
+/// Decimal values of a string, float or integer arrow array, floats are rounded to the scale.
+pub(crate) fn decimal_values(
+    array: &dyn Array,
+    scale: usize,
+) -> Result<Vec<Option<Decimal>>, errors::Error> {
+    let any = array.as_any();
+    let values: Vec<Option<Decimal>> = if let Some(a) = any.downcast_ref::<Utf8Array<i64>>() {
+        a.iter()
+            .map(|v| v.map(Decimal::from_str).transpose())
+            .collect::<Result<_, _>>()?
+    } else if let Some(a) = any.downcast_ref::<Utf8Array<i32>>() {
+        a.iter()
+            .map(|v| v.map(Decimal::from_str).transpose())
+            .collect::<Result<_, _>>()?
+    } else if let Some(a) = any.downcast_ref::<PrimitiveArray<f64>>() {
+        a.iter()
+            .map(|v| v.map(|f| float_decimal(*f, scale)).transpose())
+            .collect::<Result<_, _>>()?
+    } else if let Some(a) = any.downcast_ref::<PrimitiveArray<f32>>() {
+        a.iter()
+            .map(|v| v.map(|f| float_decimal(*f as f64, scale)).transpose())
+            .collect::<Result<_, _>>()?
+    } else if let Some(a) = any.downcast_ref::<PrimitiveArray<i64>>() {
+        a.iter()
+            .map(|v| v.map(|i| Decimal::new(*i as i128, 0)))
+            .collect()
+    } else if let Some(a) = any.downcast_ref::<PrimitiveArray<i32>>() {
+        a.iter()
+            .map(|v| v.map(|i| Decimal::new(*i as i128, 0)))
+            .collect()
+    } else if let Some(a) = any.downcast_ref::<PrimitiveArray<u64>>() {
+        a.iter()
+            .map(|v| v.map(|i| Decimal::new(*i as i128, 0)))
+            .collect()
+    } else if let Some(a) = any.downcast_ref::<PrimitiveArray<u32>>() {
+        a.iter()
+            .map(|v| v.map(|i| Decimal::new(*i as i128, 0)))
+            .collect()
+    } else {
+        return Err(errors::Error {
+            message: format!("Can not write {:?} as a decimal!", array.data_type()),
+        });
+    };
+    Ok(values)
+}
+
+/// DECIMAL(precision, scale) array of the values, returns an error if a value does not fit.
+pub(crate) fn unscaled_array(
+    values: Vec<Option<Decimal>>,
+    precision: usize,
+    scale: usize,
+) -> Result<Box<dyn Array>, errors::Error> {
+    let mut unscaled = Vec::with_capacity(values.len());
+    for value in values {
+        match value {
+            Some(d) => match d.rescale(scale as u32) {
+                Some(d) if d.digits() <= precision as u32 => unscaled.push(Some(d.unscaled())),
+                _ => {
+                    return Err(errors::Error {
+                        message: format!(
+                            "Decimal {} does not fit in DECIMAL({}, {})!",
+                            d, precision, scale
+                        ),
+                    })
+                }
+            },
+            None => unscaled.push(None),
+        }
+    }
+
+    Ok(PrimitiveArray::<i128>::from(unscaled)
+        .to(DataType::Decimal(precision, scale))
+        .boxed())
+}
+
+fn float_decimal(value: f64, scale: usize) -> Result<Decimal, errors::Error> {
+    if !value.is_finite() {
+        return Err(errors::Error {
+            message: format!("Can not write {} as a decimal!", value),
+        });
+    }
+    format!("{:.*}", scale, value).parse()
+}
@@ -1,7 +1,12 @@
 use serde::{de::DeserializeOwned, Serialize};
-use std::ops::{Add, Div};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::{Add, Div},
+};
 
-use crate::dataframe::Group;
+use crate::{dataframe::Group, errors};
 
 /// Aggregator function that sums the result from the function property.
 /// Example:
@@ -118,6 +123,82 @@ pub fn count<D: Clone + Serialize + DeserializeOwned>(group: &Group<D>) -> usize
     count
 }
 
+/// First row of the group, in the original order of the rows.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{first, last}};
+///
+/// let df = DataFrame::new(vec![('a', 1), ('b', 2), ('a', 3)]);
+/// let result = df.groupby(|x| x.0).agg(|_, g| (first(g).unwrap().1, last(g).unwrap().1));
+///
+/// assert_eq!(result.take(2), vec![(1, 3), (2, 2)]);
+/// ```
+pub fn first<'a, D: Clone + Serialize + DeserializeOwned>(group: &Group<'a, D>) -> Option<&'a D> {
+    group.iter().next()
+}
+
+/// Last row of the group, in the original order of the rows.
+pub fn last<'a, D: Clone + Serialize + DeserializeOwned>(group: &Group<'a, D>) -> Option<&'a D> {
+    group.iter().last()
+}
+
+/// Row at position `n` (starting at 0) of the group, None if the group has `n` rows or less.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{nth, all}};
+///
+/// let df = DataFrame::new(vec![10, 20, 30]);
+/// let result = df.groupby(all).agg(|_, g| (nth(g, 1).copied(), nth(g, 3).copied()));
+///
+/// assert_eq!(result.take(1), vec![(Some(20), None)]);
+/// ```
+pub fn nth<'a, D: Clone + Serialize + DeserializeOwned>(
+    group: &Group<'a, D>,
+    n: usize,
+) -> Option<&'a D> {
+    group.iter().nth(n)
+}
+
+/// Number of distinct values of the property in the group.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{nunique, all}};
+///
+/// let df = DataFrame::new(vec![('a', 1), ('b', 2), ('a', 3)]);
+/// let result = df.groupby(all).agg(|_, g| nunique(g, |x| x.0));
+///
+/// assert_eq!(result.take(1), vec![2]);
+/// ```
+pub fn nunique<D: Clone + Serialize + DeserializeOwned, N: Eq + Hash, F>(
+    group: &Group<D>,
+    property: F,
+) -> usize
+where
+    F: Fn(&D) -> N,
+{
+    group.iter().map(property).collect::<HashSet<N>>().len()
+}
+
+/// Number of rows of the group that satisfy the condition.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{count_where, all}};
+///
+/// let df = DataFrame::new(vec![1, 5, 8, 2]);
+/// let result = df.groupby(all).agg(|_, g| count_where(g, |x| *x > 2));
+///
+/// assert_eq!(result.take(1), vec![2]);
+/// ```
+pub fn count_where<D: Clone + Serialize + DeserializeOwned, F>(
+    group: &Group<D>,
+    condition: F,
+) -> usize
+where
+    F: Fn(&D) -> bool,
+{
+    group.iter().filter(|row| condition(row)).count()
+}
+
 /// Groupby function that group all rows together.
 /// Example:
 /// ```
@@ -176,7 +257,7 @@ where
     value
 }
 
-/// Calculate theminimum value of a closure applied to a group of rows.
+/// Calculate the minimum value of a closure applied to a group of rows.
 /// Example:
 /// ```
 /// use combee::{dataframe::DataFrame, functions::{min, all}};
@@ -198,7 +279,7 @@ where
     F: Fn(&D) -> N,
 {
     if group.data.len() == 0 {
-        panic!("Trying to calculate maximum value of empty DataFrame!")
+        panic!("Trying to calculate minimum value of empty DataFrame!")
     }
 
     let mut value = f(group.data.clone().find(|_| true).unwrap());
@@ -212,3 +293,385 @@ where
     }
     value
 }
+
+/// How [partial_max], [partial_min], [argmax] and [argmin] handle NaN values
+/// (any value that is not comparable with itself).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NanPolicy {
+    /// Ignore NaN values.
+    Skip,
+    /// The result is the first NaN value.
+    Propagate,
+    /// Return an error if there is a NaN value.
+    Error,
+}
+
+/// Calculate the maximum value of a closure applied to a group of rows, for partially ordered values such as floats.
+/// Returns None for an empty group, or if every value is skipped.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{partial_max, all, NanPolicy}};
+///
+/// let df = DataFrame::new(vec![1.5, f64::NAN, 3.0]);
+/// let result = df.groupby(all).agg(|_, g| partial_max(g, NanPolicy::Skip, |x| *x).unwrap()).take(1);
+///
+/// assert_eq!(result[0], Some(3.0));
+/// ```
+pub fn partial_max<D: Clone + Serialize + DeserializeOwned, N: PartialOrd, F>(
+    group: &Group<D>,
+    nan_policy: NanPolicy,
+    property: F,
+) -> Result<Option<N>, errors::Error>
+where
+    F: Fn(&D) -> N,
+{
+    Ok(extreme(group, nan_policy, property, Ordering::Greater)?.map(|(_, value)| value))
+}
+
+/// Calculate the minimum value of a closure applied to a group of rows, for partially ordered values such as floats.
+/// Returns None for an empty group, or if every value is skipped.
+pub fn partial_min<D: Clone + Serialize + DeserializeOwned, N: PartialOrd, F>(
+    group: &Group<D>,
+    nan_policy: NanPolicy,
+    property: F,
+) -> Result<Option<N>, errors::Error>
+where
+    F: Fn(&D) -> N,
+{
+    Ok(extreme(group, nan_policy, property, Ordering::Less)?.map(|(_, value)| value))
+}
+
+/// Row of the group with the maximum value of the closure, the first one on ties.
+/// Returns None for an empty group, or if every value is skipped.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{argmax, all, NanPolicy}};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     height: f64
+/// }
+///
+/// let df = DataFrame::new(vec![
+///     D { name: "jujuba".to_string(), height: 1.62 },
+///     D { name: "xpto".to_string(), height: 1.80 }
+/// ]);
+/// let tallest = df.groupby(all).agg(|_, g|
+///     argmax(g, NanPolicy::Error, |x| x.height).unwrap().unwrap().name.clone()
+/// );
+///
+/// assert_eq!(tallest.take(1), vec!["xpto".to_string()]);
+/// ```
+pub fn argmax<'a, D: Clone + Serialize + DeserializeOwned, N: PartialOrd, F>(
+    group: &Group<'a, D>,
+    nan_policy: NanPolicy,
+    property: F,
+) -> Result<Option<&'a D>, errors::Error>
+where
+    F: Fn(&D) -> N,
+{
+    Ok(extreme(group, nan_policy, property, Ordering::Greater)?.map(|(row, _)| row))
+}
+
+/// Row of the group with the minimum value of the closure, the first one on ties.
+/// Returns None for an empty group, or if every value is skipped.
+pub fn argmin<'a, D: Clone + Serialize + DeserializeOwned, N: PartialOrd, F>(
+    group: &Group<'a, D>,
+    nan_policy: NanPolicy,
+    property: F,
+) -> Result<Option<&'a D>, errors::Error>
+where
+    F: Fn(&D) -> N,
+{
+    Ok(extreme(group, nan_policy, property, Ordering::Less)?.map(|(row, _)| row))
+}
+
+/// First row with the greatest (or least) value, following the NaN policy.
+fn extreme<'a, D: Clone + Serialize + DeserializeOwned, N: PartialOrd, F>(
+    group: &Group<'a, D>,
+    nan_policy: NanPolicy,
+    property: F,
+    ordering: Ordering,
+) -> Result<Option<(&'a D, N)>, errors::Error>
+where
+    F: Fn(&D) -> N,
+{
+    let mut result: Option<(&'a D, N)> = None;
+
+    for row in group.iter() {
+        let value = property(row);
+
+        if value.partial_cmp(&value).is_none() {
+            match nan_policy {
+                NanPolicy::Skip => continue,
+                NanPolicy::Propagate => return Ok(Some((row, value))),
+                NanPolicy::Error => {
+                    return Err(errors::Error {
+                        message: "NaN value found in group!".to_string(),
+                    })
+                }
+            }
+        }
+
+        let better = match &result {
+            Some((_, current)) => value.partial_cmp(current) == Some(ordering),
+            None => true,
+        };
+        if better {
+            result = Some((row, value));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Method used by [quantile] when the quantile is between two values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interpolation {
+    /// `lower + (higher - lower) * fraction`.
+    Linear,
+    /// The lower value.
+    Lower,
+    /// The higher value.
+    Higher,
+    /// The nearest value, the one with an even position on ties.
+    Nearest,
+    /// `(lower + higher) / 2`.
+    Midpoint,
+}
+
+/// Calculate the median of the values of the property, None for an empty group.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{median, all}};
+///
+/// let df = DataFrame::new(vec![3.0, 1.0, 4.0, 10.0]);
+/// let result = df.groupby(all).agg(|_, g| median(g, |x| *x)).take(1);
+///
+/// assert_eq!(result[0], Some(3.5));
+/// ```
+pub fn median<D: Clone + Serialize + DeserializeOwned, F>(
+    group: &Group<D>,
+    property: F,
+) -> Option<f64>
+where
+    F: Fn(&D) -> f64,
+{
+    quantile(group, 0.5, Interpolation::Linear, property)
+}
+
+/// Calculate the quantile `q` (between 0 and 1) of the values of the property.
+/// Returns None for an empty group or a `q` outside of [0, 1]. NaN values, whatever their sign, are sorted after every number.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{quantile, all, Interpolation}};
+///
+/// let df = DataFrame::new(vec![1.0, 2.0, 3.0, 4.0]);
+/// let result = df.groupby(all).agg(|_, g| (
+///     quantile(g, 0.25, Interpolation::Linear, |x| *x),
+///     quantile(g, 0.25, Interpolation::Higher, |x| *x)
+/// )).take(1);
+///
+/// assert_eq!(result[0], (Some(1.75), Some(2.0)));
+/// ```
+pub fn quantile<D: Clone + Serialize + DeserializeOwned, F>(
+    group: &Group<D>,
+    q: f64,
+    interpolation: Interpolation,
+    property: F,
+) -> Option<f64>
+where
+    F: Fn(&D) -> f64,
+{
+    if !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+
+    let mut values = values(group, property);
+    if values.is_empty() {
+        return None;
+    }
+    // total_cmp sorts NaN values with a negative sign first, every NaN goes last instead.
+    values.sort_by(|a, b| match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.total_cmp(b),
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+    });
+
+    let position = q * (values.len() - 1) as f64;
+    let (lower, higher) = (position.floor() as usize, position.ceil() as usize);
+    let fraction = position - lower as f64;
+
+    Some(match interpolation {
+        Interpolation::Linear => values[lower] + (values[higher] - values[lower]) * fraction,
+        Interpolation::Lower => values[lower],
+        Interpolation::Higher => values[higher],
+        Interpolation::Nearest => {
+            if fraction < 0.5 || (fraction == 0.5 && lower % 2 == 0) {
+                values[lower]
+            } else {
+                values[higher]
+            }
+        }
+        Interpolation::Midpoint => (values[lower] + values[higher]) / 2.0,
+    })
+}
+
+/// Calculate the variance of the values of the property with `ddof` delta degrees of freedom:
+/// the sum of the squared deviations is divided by `n - ddof` (1 for the sample variance, 0 for the population).
+/// Returns None when the group has `ddof` rows or less.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{variance, std, all}};
+///
+/// let df = DataFrame::new(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+/// let result = df.groupby(all).agg(|_, g| (variance(g, 0, |x| *x), std(g, 0, |x| *x))).take(1);
+///
+/// assert_eq!(result[0], (Some(4.0), Some(2.0)));
+/// ```
+pub fn variance<D: Clone + Serialize + DeserializeOwned, F>(
+    group: &Group<D>,
+    ddof: usize,
+    property: F,
+) -> Option<f64>
+where
+    F: Fn(&D) -> f64,
+{
+    let values = values(group, property);
+    if values.len() <= ddof {
+        return None;
+    }
+
+    let (m2, _, _) = central_moments(&values);
+    Some(m2 * values.len() as f64 / (values.len() - ddof) as f64)
+}
+
+/// Calculate the standard deviation of the values of the property with `ddof` delta degrees of freedom,
+/// see [variance].
+pub fn std<D: Clone + Serialize + DeserializeOwned, F>(
+    group: &Group<D>,
+    ddof: usize,
+    property: F,
+) -> Option<f64>
+where
+    F: Fn(&D) -> f64,
+{
+    variance(group, ddof, property).map(f64::sqrt)
+}
+
+/// Most frequent value of the property, the first one to appear in the group on ties.
+/// Returns None for an empty group.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{mode, all}};
+///
+/// let df = DataFrame::new(vec!['b', 'a', 'a', 'b', 'c']);
+/// let result = df.groupby(all).agg(|_, g| mode(g, |x| *x)).take(1);
+///
+/// assert_eq!(result[0], Some('b'));
+/// ```
+pub fn mode<D: Clone + Serialize + DeserializeOwned, N: Eq + Hash + Clone, F>(
+    group: &Group<D>,
+    property: F,
+) -> Option<N>
+where
+    F: Fn(&D) -> N,
+{
+    let mut counts: HashMap<N, (usize, usize)> = HashMap::new();
+    for (position, row) in group.iter().enumerate() {
+        counts.entry(property(row)).or_insert((0, position)).0 += 1;
+    }
+
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1 .0.cmp(&b.1 .0).then(b.1 .1.cmp(&a.1 .1)))
+        .map(|(value, _)| value)
+}
+
+/// Calculate the sample skewness of the values of the property (adjusted Fisher-Pearson, as in pandas).
+/// Returns None for groups with less than 3 rows, and 0 when every value is equal.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{skew, all}};
+///
+/// let df = DataFrame::new(vec![1.0, 2.0, 3.0]);
+/// let result = df.groupby(all).agg(|_, g| skew(g, |x| *x)).take(1);
+///
+/// assert_eq!(result[0], Some(0.0));
+/// ```
+pub fn skew<D: Clone + Serialize + DeserializeOwned, F>(
+    group: &Group<D>,
+    property: F,
+) -> Option<f64>
+where
+    F: Fn(&D) -> f64,
+{
+    let values = values(group, property);
+    let n = values.len() as f64;
+    if values.len() < 3 {
+        return None;
+    }
+
+    let (m2, m3, _) = central_moments(&values);
+    if m2 == 0.0 {
+        return Some(0.0);
+    }
+    Some((n * (n - 1.0)).sqrt() / (n - 2.0) * m3 / m2.powf(1.5))
+}
+
+/// Calculate the sample excess kurtosis of the values of the property (bias corrected, as in pandas).
+/// Returns None for groups with less than 4 rows, and 0 when every value is equal.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::{kurtosis, all}};
+///
+/// let df = DataFrame::new(vec![1.0, 2.0, 3.0, 4.0]);
+/// let result = df.groupby(all).agg(|_, g| kurtosis(g, |x| *x)).take(1);
+///
+/// assert!((result[0].unwrap() + 1.2).abs() < 1e-12);
+/// ```
+pub fn kurtosis<D: Clone + Serialize + DeserializeOwned, F>(
+    group: &Group<D>,
+    property: F,
+) -> Option<f64>
+where
+    F: Fn(&D) -> f64,
+{
+    let values = values(group, property);
+    let n = values.len() as f64;
+    if values.len() < 4 {
+        return None;
+    }
+
+    let (m2, _, m4) = central_moments(&values);
+    if m2 == 0.0 {
+        return Some(0.0);
+    }
+    let g2 = m4 / (m2 * m2) - 3.0;
+    Some(((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)))
+}
+
+fn values<D: Clone + Serialize + DeserializeOwned, F>(group: &Group<D>, property: F) -> Vec<f64>
+where
+    F: Fn(&D) -> f64,
+{
+    group.iter().map(property).collect()
+}
+
+/// Second, third and fourth central moments (divided by n) of non empty values.
+fn central_moments(values: &[f64]) -> (f64, f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+
+    let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+    for value in values {
+        let deviation = value - mean;
+        m2 += deviation * deviation;
+        m3 += deviation * deviation * deviation;
+        m4 += deviation * deviation * deviation * deviation;
+    }
+    (m2 / n, m3 / n, m4 / n)
+}
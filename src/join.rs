@@ -0,0 +1,322 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime};
+
+/// Kind of join between two DataFrames, it sets the type of the joined rows.
+/// Use one of [Inner], [Left], [Right] or [Outer] with [crate::dataframe::DataFrame::join].
+pub trait JoinType<D, E> {
+    /// Type of the joined rows.
+    type Output: Clone + DeserializeOwned + Serialize;
+
+    /// Whether the left rows without a match are kept.
+    const KEEP_LEFT: bool;
+
+    /// Whether the right rows without a match are kept.
+    const KEEP_RIGHT: bool;
+
+    /// Builds a joined row, a side is None only for kept rows without a match.
+    fn row(left: Option<&D>, right: Option<&E>) -> Self::Output;
+}
+
+/// Inner join, only rows with a match on both sides: `(D, E)`.
+#[derive(Clone, Copy, Debug)]
+pub struct Inner;
+
+/// Left join, every left row and the right rows that match: `(D, Option<E>)`.
+#[derive(Clone, Copy, Debug)]
+pub struct Left;
+
+/// Right join, every right row and the left rows that match: `(Option<D>, E)`.
+#[derive(Clone, Copy, Debug)]
+pub struct Right;
+
+/// Full outer join, every row of both sides: `(Option<D>, Option<E>)`.
+#[derive(Clone, Copy, Debug)]
+pub struct Outer;
+
+impl<D, E> JoinType<D, E> for Inner
+where
+    D: Clone + DeserializeOwned + Serialize,
+    E: Clone + DeserializeOwned + Serialize,
+{
+    type Output = (D, E);
+    const KEEP_LEFT: bool = false;
+    const KEEP_RIGHT: bool = false;
+
+    fn row(left: Option<&D>, right: Option<&E>) -> (D, E) {
+        match (left, right) {
+            (Some(l), Some(r)) => (l.clone(), r.clone()),
+            _ => unreachable!("inner joins only build matched rows"),
+        }
+    }
+}
+
+impl<D, E> JoinType<D, E> for Left
+where
+    D: Clone + DeserializeOwned + Serialize,
+    E: Clone + DeserializeOwned + Serialize,
+{
+    type Output = (D, Option<E>);
+    const KEEP_LEFT: bool = true;
+    const KEEP_RIGHT: bool = false;
+
+    fn row(left: Option<&D>, right: Option<&E>) -> (D, Option<E>) {
+        match left {
+            Some(l) => (l.clone(), right.cloned()),
+            None => unreachable!("left joins always have a left row"),
+        }
+    }
+}
+
+impl<D, E> JoinType<D, E> for Right
+where
+    D: Clone + DeserializeOwned + Serialize,
+    E: Clone + DeserializeOwned + Serialize,
+{
+    type Output = (Option<D>, E);
+    const KEEP_LEFT: bool = false;
+    const KEEP_RIGHT: bool = true;
+
+    fn row(left: Option<&D>, right: Option<&E>) -> (Option<D>, E) {
+        match right {
+            Some(r) => (left.cloned(), r.clone()),
+            None => unreachable!("right joins always have a right row"),
+        }
+    }
+}
+
+impl<D, E> JoinType<D, E> for Outer
+where
+    D: Clone + DeserializeOwned + Serialize,
+    E: Clone + DeserializeOwned + Serialize,
+{
+    type Output = (Option<D>, Option<E>);
+    const KEEP_LEFT: bool = true;
+    const KEEP_RIGHT: bool = true;
+
+    fn row(left: Option<&D>, right: Option<&E>) -> (Option<D>, Option<E>) {
+        (left.cloned(), right.cloned())
+    }
+}
+
+/// Direction used by as-of joins to find the right row of each left row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AsofDirection {
+    /// Last right row with a key less than or equal to the left key.
+    Backward,
+    /// First right row with a key greater than or equal to the left key.
+    Forward,
+    /// Right row with the closest key, the backward one on ties.
+    Nearest,
+}
+
+/// Key of as-of joins: an ordered value with a distance, compared with the tolerance of the join.
+/// Implemented for integers, floats and the `time` dates, datetimes and durations.
+pub trait AsofKey: PartialOrd + Clone {
+    /// Type of the distance between two keys, e.g. `time::Duration` for dates.
+    type Distance: PartialOrd;
+
+    /// Distance from `other` to this key, with `other <= self`. Returns None when it does not fit the type.
+    fn distance(&self, other: &Self) -> Option<Self::Distance>;
+}
+
+macro_rules! impl_checked_asof_key {
+    ($($t:ty),*) => {
+        $(
+            impl AsofKey for $t {
+                type Distance = $t;
+
+                fn distance(&self, other: &Self) -> Option<$t> {
+                    self.checked_sub(*other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_asof_key {
+    ($($t:ty => $distance:ty),*) => {
+        $(
+            impl AsofKey for $t {
+                type Distance = $distance;
+
+                fn distance(&self, other: &Self) -> Option<$distance> {
+                    Some(*self - *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_asof_key!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, Duration);
+impl_asof_key!(
+    f32 => f32,
+    f64 => f64,
+    Date => Duration,
+    PrimitiveDateTime => Duration,
+    OffsetDateTime => Duration
+);
+
+/// Hash join of the rows on their keys, the right rows are hashed.
+/// Rows follow the order of the left rows, each one followed by its matches in right order;
+/// kept right rows without a match are added at the end in right order.
+pub(crate) fn hash_join<D, E, I, FL, FR, J>(
+    left: &[D],
+    right: &[E],
+    left_key: FL,
+    right_key: FR,
+) -> Vec<J::Output>
+where
+    I: Eq + Hash,
+    FL: Fn(&D) -> I,
+    FR: Fn(&E) -> I,
+    J: JoinType<D, E>,
+{
+    let mut table: HashMap<I, Vec<usize>> = HashMap::new();
+    for (i, row) in right.iter().enumerate() {
+        table.entry(right_key(row)).or_default().push(i);
+    }
+
+    let mut matched = vec![false; right.len()];
+    let mut rows = Vec::new();
+    for row in left.iter() {
+        match table.get(&left_key(row)) {
+            Some(indexes) => {
+                for i in indexes.iter() {
+                    matched[*i] = true;
+                    rows.push(J::row(Some(row), Some(&right[*i])));
+                }
+            }
+            None => {
+                if J::KEEP_LEFT {
+                    rows.push(J::row(Some(row), None));
+                }
+            }
+        }
+    }
+
+    if J::KEEP_RIGHT {
+        for (i, row) in right.iter().enumerate() {
+            if !matched[i] {
+                rows.push(J::row(None, Some(row)));
+            }
+        }
+    }
+
+    rows
+}
+
+/// Left rows whose key is (semi join) or is not (anti join) a key of the right rows, in left order.
+pub(crate) fn key_filter<D, E, I, FL, FR>(
+    left: &[D],
+    right: &[E],
+    left_key: FL,
+    right_key: FR,
+    keep_matches: bool,
+) -> Vec<D>
+where
+    D: Clone,
+    I: Eq + Hash,
+    FL: Fn(&D) -> I,
+    FR: Fn(&E) -> I,
+{
+    let keys: HashSet<I> = right.iter().map(right_key).collect();
+
+    left.iter()
+        .filter(|row| keys.contains(&left_key(row)) == keep_matches)
+        .cloned()
+        .collect()
+}
+
+/// As-of join of the rows: each left row with the closest right row in the direction, among the right rows
+/// with the same `by` key and within the tolerance. Rows follow the order of the left rows.
+/// Keys that are not comparable with themselves (e.g. NaN) never match.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn asof_join<D, E, I, K, BL, BR, FL, FR>(
+    left: &[D],
+    right: &[E],
+    left_by: BL,
+    right_by: BR,
+    left_on: FL,
+    right_on: FR,
+    direction: AsofDirection,
+    tolerance: Option<K::Distance>,
+) -> Vec<(D, Option<E>)>
+where
+    D: Clone,
+    E: Clone,
+    I: Eq + Hash,
+    K: AsofKey,
+    BL: Fn(&D) -> I,
+    BR: Fn(&E) -> I,
+    FL: Fn(&D) -> K,
+    FR: Fn(&E) -> K,
+{
+    let comparable = |k: &K| k.partial_cmp(k).is_some();
+
+    let mut table: HashMap<I, Vec<(K, usize)>> = HashMap::new();
+    for (i, row) in right.iter().enumerate() {
+        let key = right_on(row);
+        if comparable(&key) {
+            table.entry(right_by(row)).or_default().push((key, i));
+        }
+    }
+    // Stable, so already sorted inputs keep their order for equal keys.
+    for keys in table.values_mut() {
+        keys.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+    }
+
+    // A distance too large for its type is outside any tolerance.
+    let within = |distance: &Option<K::Distance>| match (distance, &tolerance) {
+        (_, None) => true,
+        (Some(d), Some(t)) => d <= t,
+        (None, Some(_)) => false,
+    };
+
+    left.iter()
+        .map(|row| {
+            let key = left_on(row);
+            let keys = match table.get(&left_by(row)) {
+                Some(k) if comparable(&key) => k,
+                _ => return (row.clone(), None),
+            };
+
+            let after = keys.partition_point(|(k, _)| *k <= key);
+            let backward = after
+                .checked_sub(1)
+                .map(|i| (&keys[i], key.distance(&keys[i].0)))
+                .filter(|(_, distance)| within(distance));
+            let start = keys.partition_point(|(k, _)| *k < key);
+            let forward = keys
+                .get(start)
+                .map(|k| (k, k.0.distance(&key)))
+                .filter(|(_, distance)| within(distance));
+
+            let found = match direction {
+                AsofDirection::Backward => backward,
+                AsofDirection::Forward => forward,
+                AsofDirection::Nearest => match (backward, forward) {
+                    (Some(b), Some(f)) => {
+                        let closer = match (&f.1, &b.1) {
+                            (Some(f), Some(b)) => f < b,
+                            (Some(_), None) => true,
+                            _ => false,
+                        };
+                        if closer {
+                            Some(f)
+                        } else {
+                            Some(b)
+                        }
+                    }
+                    (b, f) => b.or(f),
+                },
+            };
+
+            (row.clone(), found.map(|((_, i), _)| right[*i].clone()))
+        })
+        .collect()
+}
@@ -12,20 +12,37 @@
 //!
 //! 4. [function@functions::avg] for calculating average value of group of rows.
 //!
+use arrow2::{array::Array, chunk::Chunk, datatypes::Field, io::ipc};
+use bytes::Bytes;
 use csv;
 use log;
 use parquet::{
     arrow::arrow_reader::ParquetRecordBatchReaderBuilder,
-    file::serialized_reader::SerializedFileReader,
+    basic::{ConvertedType, LogicalType, Type as PhysicalType},
+    file::{
+        reader::{ChunkReader, FileReader},
+        serialized_reader::SerializedFileReader,
+    },
+    record::reader::RowIter,
+    schema::types::{SchemaDescriptor, Type},
 };
 use serde::{de::DeserializeOwned, Serialize};
+use serde_arrow::arrow2::deserialize_from_arrays;
 use std::{
     fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
+    io::{BufRead, BufReader, Read, Seek},
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use crate::{dataframe::DataFrame, parquet_deserializer::from_row};
+use crate::{
+    dataframe::DataFrame,
+    options::{CsvReadOptions, ParquetReadOptions},
+    parquet_deserializer::from_row_with_partitions,
+    partition::{partition_files, Partition},
+    predicate::Predicate,
+    stream::CsvRowIter,
+};
 
 /// DataFrame module, contains all the basic functions (groupby, agg, find...).
 pub mod dataframe;
@@ -36,7 +53,37 @@ pub mod functions;
 /// Error module.
 pub mod errors;
 
+/// Options module, contains the configurations used by the readers and writers.
+pub mod options;
+
+/// Streaming module, contains the iterators returned by the lazy readers.
+pub mod stream;
+
+/// Decimal module, contains the fixed point number used to read and write parquet DECIMAL columns.
+pub mod decimal;
+
+/// Predicate module, contains the column conditions used to filter parquet files while reading.
+pub mod predicate;
+
+/// Aggregators module, contains composable aggregations computed in a single pass over a group.
+pub mod aggregators;
+
+/// Join module, contains the kinds of joins between DataFrames (inner, left, right and outer).
+pub mod join;
+
+/// Temporal module, contains the serde helpers to read and write `time` dates and datetimes as parquet DATE and TIMESTAMP columns.
+/// Datetimes are truncated to microseconds and OffsetDateTime values are read back in UTC.
+pub mod temporal;
+
+/// Sketches module, contains mergeable sketches for approximate distinct counts and quantiles.
+pub mod sketches;
+
+mod csv_serializer;
 mod parquet_deserializer;
+mod partition;
+mod probe;
+mod projection;
+mod sample;
 
 /// Read a CSV file, the data parameter D must be compatible with the columns of the csv.
 /// The first row of the CSV must be the header.
@@ -67,30 +114,395 @@ mod parquet_deserializer;
 /// ```
 pub fn read_csv<D: Clone + DeserializeOwned + Serialize>(
     path: String,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    read_csv_with(path, &CsvReadOptions::default())
+}
+
+/// Read a CSV file using custom options (delimiter, quoting, headerless files, comments...).
+/// # Examples
+/// Suppose we have a semicolon separated file without a header like this:
+/// ```csv
+/// Daniel;26
+/// Sergio;30
+/// ```
+/// Then the columns are matched to the fields of the struct by position:
+/// ```no_run
+/// use combee::{read_csv_with, options::CsvReadOptions};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: usize
+/// }
+///
+/// let options = CsvReadOptions::new().delimiter(b';').has_headers(false);
+/// let df = read_csv_with::<D>("dataset.csv".to_string(), &options).unwrap();
+/// ```
+pub fn read_csv_with<D: Clone + DeserializeOwned + Serialize>(
+    path: String,
+    options: &CsvReadOptions,
 ) -> Result<dataframe::DataFrame<D>, errors::Error> {
     log::debug!("Reading CSV at path '{}'", path);
-    match csv::Reader::from_path(path) {
-        Ok(mut reader) => {
-            let mut data = Vec::new();
-
-            for result in reader.deserialize::<D>() {
-                let row = match result {
-                    Ok(row) => row,
-                    Err(e) => {
-                        return Err(errors::Error {
-                            message: e.to_string(),
-                        })
-                    }
-                };
+    read_csv_from_reader(open_csv_file(&path)?, options)
+}
+
+/// Read a CSV from any reader (stdin, in-memory buffers, decompressed streams...).
+/// Example:
+/// ```
+/// use combee::{read_csv_from_reader, options::CsvReadOptions};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: usize
+/// }
+///
+/// let data = "name,age\nDaniel,26\nSergio,30\n";
+/// let df = read_csv_from_reader::<D, _>(data.as_bytes(), &CsvReadOptions::default()).unwrap();
+/// assert_eq!(df.len(), 2);
+/// ```
+pub fn read_csv_from_reader<D: Clone + DeserializeOwned + Serialize, R: Read>(
+    reader: R,
+    options: &CsvReadOptions,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    let mut reader = csv_reader(reader, options)?;
+    let mut data = Vec::new();
 
-                log::trace!("Read one row of CSV, loading into the array...");
-                data.push(row);
+    for result in reader.deserialize::<D>() {
+        let row = match result {
+            Ok(row) => row,
+            Err(e) => {
+                return Err(errors::Error {
+                    message: e.to_string(),
+                })
             }
+        };
+
+        log::trace!("Read one row of CSV, loading into the array...");
+        data.push(row);
+    }
+
+    Ok(dataframe::DataFrame::new(data))
+}
+
+/// Read a CSV file lazily, returning an iterator over the typed rows instead of a DataFrame.
+/// Only one row is kept in memory at a time, use [stream::CsvRowIter::chunks] to process the file in batches.
+/// Example:
+/// ```
+/// use combee::scan_csv;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: u32
+/// }
+///
+/// let mut total = 0;
+/// for row in scan_csv::<D>("dataset.csv".to_string()).unwrap() {
+///     total += row.unwrap().age;
+/// }
+/// ```
+pub fn scan_csv<D: Clone + DeserializeOwned + Serialize>(
+    path: String,
+) -> Result<CsvRowIter<D, BufReader<File>>, errors::Error> {
+    scan_csv_with(path, &CsvReadOptions::default())
+}
+
+/// Read a CSV file lazily using custom options, see [function@scan_csv] and [function@read_csv_with].
+pub fn scan_csv_with<D: Clone + DeserializeOwned + Serialize>(
+    path: String,
+    options: &CsvReadOptions,
+) -> Result<CsvRowIter<D, BufReader<File>>, errors::Error> {
+    log::debug!("Scanning CSV at path '{}'", path);
+    scan_csv_from_reader(open_csv_file(&path)?, options)
+}
+
+/// Read a CSV lazily from any reader, see [function@scan_csv] and [function@read_csv_from_reader].
+pub fn scan_csv_from_reader<D: Clone + DeserializeOwned + Serialize, R: Read>(
+    reader: R,
+    options: &CsvReadOptions,
+) -> Result<CsvRowIter<D, BufReader<R>>, errors::Error> {
+    Ok(CsvRowIter::new(csv_reader(reader, options)?))
+}
+
+fn open_csv_file(path: &str) -> Result<File, errors::Error> {
+    match File::open(path) {
+        Ok(f) => Ok(f),
+        Err(_) => Err(errors::Error {
+            message: format!("Could not open CSV file at path {}!", path),
+        }),
+    }
+}
 
-            Ok(dataframe::DataFrame::new(data))
+fn csv_reader<R: Read>(
+    reader: R,
+    options: &CsvReadOptions,
+) -> Result<csv::Reader<BufReader<R>>, errors::Error> {
+    let mut buffer = BufReader::new(reader);
+    let mut line = String::new();
+    for _ in 0..options.skip_rows {
+        line.clear();
+        if let Err(e) = buffer.read_line(&mut line) {
+            return Err(errors::Error {
+                message: e.to_string(),
+            });
         }
+    }
+
+    Ok(options.reader_builder().from_reader(buffer))
+}
+
+/// Read a JSON file containing an array of objects, nested objects and arrays map to nested structs and vectors.
+/// Parse errors report the line and column where they happened.
+/// ```no_run
+/// use combee::read_json;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct Address {
+///     city: String
+/// }
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     address: Address
+/// }
+///
+/// let df = read_json::<D>("dataset.json".to_string()).unwrap();
+/// ```
+pub fn read_json<D: Clone + DeserializeOwned + Serialize>(
+    path: String,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    log::debug!("Reading JSON at path '{}'", path);
+    read_json_from_reader(open_json_file(&path)?)
+}
+
+/// Read a JSON array of objects from any reader, see [read_json].
+pub fn read_json_from_reader<D: Clone + DeserializeOwned + Serialize, R: Read>(
+    reader: R,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    match serde_json::from_reader::<_, Vec<D>>(BufReader::new(reader)) {
+        Ok(data) => Ok(dataframe::DataFrame::new(data)),
         Err(e) => Err(errors::Error {
+            message: format!(
+                "Error parsing JSON at line {}, column {}: {}",
+                e.line(),
+                e.column(),
+                json_error_message(&e)
+            ),
+        }),
+    }
+}
+
+/// Read a newline delimited JSON file, one object per line, blank lines are skipped.
+/// Parse errors report the line number of the invalid object.
+/// ```no_run
+/// use combee::read_ndjson;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     tags: Vec<String>
+/// }
+///
+/// let df = read_ndjson::<D>("dataset.ndjson".to_string()).unwrap();
+/// ```
+pub fn read_ndjson<D: Clone + DeserializeOwned + Serialize>(
+    path: String,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    log::debug!("Reading NDJSON at path '{}'", path);
+    read_ndjson_from_reader(open_json_file(&path)?)
+}
+
+/// Read newline delimited JSON from any reader, see [read_ndjson].
+pub fn read_ndjson_from_reader<D: Clone + DeserializeOwned + Serialize, R: Read>(
+    reader: R,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    let mut data = Vec::new();
+
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = match line {
+            Ok(l) => l,
+            Err(e) => {
+                return Err(errors::Error {
+                    message: format!("Error reading NDJSON at line {}: {}", i + 1, e),
+                })
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<D>(&line) {
+            Ok(row) => data.push(row),
+            Err(e) => {
+                return Err(errors::Error {
+                    message: format!(
+                        "Error parsing NDJSON at line {}, column {}: {}",
+                        i + 1,
+                        e.column(),
+                        json_error_message(&e)
+                    ),
+                })
+            }
+        }
+    }
+
+    Ok(dataframe::DataFrame::new(data))
+}
+
+/// Error message of serde_json without its position, which is reported separately.
+fn json_error_message(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    let position = format!(" at line {} column {}", error.line(), error.column());
+    match message.strip_suffix(&position) {
+        Some(m) => m.to_string(),
+        None => message,
+    }
+}
+
+fn open_json_file(path: &str) -> Result<File, errors::Error> {
+    match File::open(path) {
+        Ok(f) => Ok(f),
+        Err(_) => Err(errors::Error {
+            message: format!("Could not open JSON file at path {}!", path),
+        }),
+    }
+}
+
+/// Read an Arrow IPC file (Feather v2) or stream, the format is detected from the content of the file.
+/// Compressed record batches (LZ4 or Zstd) are decompressed while reading.
+/// ```no_run
+/// use combee::read_ipc;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: u32
+/// }
+///
+/// let df = read_ipc::<D>("dataset.arrow".to_string()).unwrap();
+/// ```
+pub fn read_ipc<D: Clone + DeserializeOwned + Serialize>(
+    path: String,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    log::debug!("Reading Arrow IPC at path '{}'", path);
+    match File::open(&path) {
+        Ok(file) => read_ipc_from_reader(BufReader::new(file)),
+        Err(_) => Err(errors::Error {
+            message: format!("Could not open Arrow IPC file at path {}!", path),
+        }),
+    }
+}
+
+/// Read Arrow IPC data in file or stream format from any seekable reader, see [read_ipc].
+/// Use [read_ipc_stream] for streams that can not seek.
+pub fn read_ipc_from_reader<D: Clone + DeserializeOwned + Serialize, R: Read + Seek>(
+    mut reader: R,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    let to_error = |e: arrow2::error::Error| errors::Error {
+        message: e.to_string(),
+    };
+
+    // Files start with the magic bytes, streams start with the schema message.
+    let mut magic = [0u8; 6];
+    let is_file = match reader.read_exact(&mut magic) {
+        Ok(_) => &magic == b"ARROW1",
+        Err(_) => false,
+    };
+    if let Err(e) = reader.rewind() {
+        return Err(errors::Error {
             message: e.to_string(),
+        });
+    }
+
+    let mut data = Vec::new();
+    if is_file {
+        let metadata = ipc::read::read_file_metadata(&mut reader).map_err(to_error)?;
+        let (projection, fields) = ipc_projection::<D>(&metadata.schema.fields);
+        for chunk in ipc::read::FileReader::new(reader, metadata, projection, None) {
+            data.append(&mut deserialize_ipc_chunk(
+                &fields,
+                &chunk.map_err(to_error)?,
+            )?);
+        }
+    } else {
+        return read_ipc_stream(reader);
+    }
+
+    Ok(dataframe::DataFrame::new(data))
+}
+
+/// Read Arrow IPC data in stream format from a reader that can not seek, such as stdin or a pipe.
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, options::{IpcFormat, IpcWriteOptions}, read_ipc_stream};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: u32
+/// }
+///
+/// let df = DataFrame::new(vec![D { name: "Daniel".to_string(), age: 26 }]);
+/// let mut buffer = Vec::new();
+/// df.write_ipc_with(&mut buffer, &IpcWriteOptions::new().format(IpcFormat::Stream)).unwrap();
+///
+/// let new_df = read_ipc_stream::<D, _>(buffer.as_slice()).unwrap();
+/// assert_eq!(new_df.len(), 1);
+/// ```
+pub fn read_ipc_stream<D: Clone + DeserializeOwned + Serialize, R: Read>(
+    mut reader: R,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    let to_error = |e: arrow2::error::Error| errors::Error {
+        message: e.to_string(),
+    };
+
+    let metadata = ipc::read::read_stream_metadata(&mut reader).map_err(to_error)?;
+    let (projection, fields) = ipc_projection::<D>(&metadata.schema.fields);
+    let mut data = Vec::new();
+    for state in ipc::read::StreamReader::new(reader, metadata, projection) {
+        if let ipc::read::StreamState::Some(chunk) = state.map_err(to_error)? {
+            data.append(&mut deserialize_ipc_chunk(&fields, &chunk)?);
+        }
+    }
+
+    Ok(dataframe::DataFrame::new(data))
+}
+
+/// Indices and fields of the IPC columns used by the fields of the struct D, None to read every column.
+fn ipc_projection<D: DeserializeOwned>(fields: &[Field]) -> (Option<Vec<usize>>, Vec<Field>) {
+    let names = match projection::struct_fields::<D>() {
+        Some(names) => names,
+        None => return (None, fields.to_vec()),
+    };
+
+    let projection: Vec<usize> = (0..fields.len())
+        .filter(|i| names.contains(&fields[*i].name.as_str()))
+        .collect();
+    if projection.is_empty() || projection.len() == fields.len() {
+        return (None, fields.to_vec());
+    }
+
+    let projected = projection.iter().map(|i| fields[*i].clone()).collect();
+    (Some(projection), projected)
+}
+
+fn deserialize_ipc_chunk<D: DeserializeOwned>(
+    fields: &[Field],
+    chunk: &Chunk<Box<dyn Array>>,
+) -> Result<Vec<D>, errors::Error> {
+    match deserialize_from_arrays(fields, chunk.arrays()) {
+        Ok(rows) => Ok(rows),
+        Err(e) => Err(errors::Error {
+            message: e.message().to_string(),
         }),
     }
 }
@@ -120,12 +532,457 @@ pub fn read_csv<D: Clone + DeserializeOwned + Serialize>(
 /// ```
 pub fn read_parquet<D: Clone + DeserializeOwned + Serialize>(
     path: String,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    read_parquet_with(path, &ParquetReadOptions::default())
+}
+
+/// Read an Apache Parquet file using custom options.
+/// Only the columns used by the struct D are decoded, unless an explicit list of columns is given.
+/// Example:
+/// ```
+/// use combee::{read_parquet_with, options::ParquetReadOptions};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: Option<u32>
+/// }
+///
+/// let options = ParquetReadOptions::new().columns(&["name"]);
+/// let df = read_parquet_with::<D>("tests/fixtures/basic.parquet".to_string(), &options).unwrap();
+/// assert!(df.find(|row| row.age.is_some()).is_none());
+/// ```
+pub fn read_parquet_with<D: Clone + DeserializeOwned + Serialize>(
+    path: String,
+    options: &ParquetReadOptions,
 ) -> Result<dataframe::DataFrame<D>, errors::Error> {
     log::debug!("Reading Parquet at path '{}'", path);
     let p: &Path = Path::new(&path);
 
     if let Ok(file) = File::open(&p) {
-        let reader = match SerializedFileReader::new(file) {
+        read_parquet_chunks(file, options, &[])
+    } else {
+        return Err(errors::Error {
+            message: format!("Could not open file {}", path),
+        });
+    }
+}
+
+/// Read the rows of an Apache Parquet file that match all the predicates.
+/// The min/max statistics of each row group are used to skip the row groups without matching rows,
+/// then the predicates are checked for every remaining row, so the result is exact.
+/// Returns an error if a predicate uses a column that is not in the file, or a value that can not be
+/// compared with the column (e.g. a string with an integer column).
+/// Example:
+/// ```
+/// use combee::{read_parquet_filtered, predicate::Predicate};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: u32
+/// }
+///
+/// let predicates = [Predicate::ge("age", 26), Predicate::is_in("name", vec!["Daniel", "Leticia"])];
+/// let df = read_parquet_filtered::<D>("tests/fixtures/basic.parquet".to_string(), &predicates).unwrap();
+/// assert_eq!(df.len(), 1);
+/// ```
+pub fn read_parquet_filtered<D: Clone + DeserializeOwned + Serialize>(
+    path: String,
+    predicates: &[Predicate],
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    log::debug!("Reading Parquet at path '{}' with predicates", path);
+
+    match File::open(&path) {
+        Ok(file) => read_parquet_chunks(file, &ParquetReadOptions::default(), predicates),
+        Err(_) => Err(errors::Error {
+            message: format!("Could not open file {}", path),
+        }),
+    }
+}
+
+/// Read all the Apache Parquet files of a directory, or matching a glob pattern, as a single DataFrame.
+/// The columns read from every file must have the same types. Files in a directory are read in name order,
+/// ignoring the files that do not end with `.parquet` or start with `_` or `.` (e.g. `_SUCCESS`).
+/// Example:
+/// ```
+/// use combee::{read_parquet_dataset, read_parquet_dataset_with, options::ParquetReadOptions};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: u32
+/// }
+///
+/// let df = read_parquet_dataset::<D>("tests/fixtures/basic.parquet".to_string()).unwrap();
+/// assert_eq!(df.len(), 3);
+///
+/// let options = ParquetReadOptions::new().parallel(true);
+/// let df = read_parquet_dataset_with::<D>("tests/fixtures/basic*.parquet".to_string(), &options).unwrap();
+/// assert_eq!(df.len(), 3);
+/// ```
+pub fn read_parquet_dataset<D: Clone + DeserializeOwned + Serialize + Send>(
+    path: String,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    read_parquet_dataset_with(path, &ParquetReadOptions::default())
+}
+
+/// Read all the Apache Parquet files of a directory, or matching a glob pattern, using custom options.
+/// With [ParquetReadOptions::parallel] the files are read by a pool of threads.
+pub fn read_parquet_dataset_with<D: Clone + DeserializeOwned + Serialize + Send>(
+    path: String,
+    options: &ParquetReadOptions,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    log::debug!("Reading Parquet dataset at '{}'", path);
+
+    let files = dataset_paths(&path)?
+        .into_iter()
+        .map(|p| (p, Partition::new()))
+        .collect();
+    read_parquet_files(files, options)
+}
+
+/// Read a Hive-style partitioned Apache Parquet dataset, e.g. written by [dataframe::DataFrame::to_parquet_partitioned].
+/// The values in the `column=value` directory names are added to the rows of the files inside them,
+/// so partition columns can be fields of the struct D.
+/// Example:
+/// ```no_run
+/// use combee::read_parquet_partitioned;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct Sale {
+///     year: i32,
+///     month: Option<u8>,
+///     amount: f64
+/// }
+///
+/// // sales/year=2023/month=1/part-00000.parquet, sales/year=2023/month=2/part-00000.parquet...
+/// let df = read_parquet_partitioned::<Sale>("sales".to_string()).unwrap();
+/// ```
+pub fn read_parquet_partitioned<D: Clone + DeserializeOwned + Serialize + Send>(
+    root: String,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    read_parquet_partitioned_with(root, &ParquetReadOptions::default())
+}
+
+/// Read a Hive-style partitioned Apache Parquet dataset using custom options.
+pub fn read_parquet_partitioned_with<D: Clone + DeserializeOwned + Serialize + Send>(
+    root: String,
+    options: &ParquetReadOptions,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    log::debug!("Reading partitioned Parquet at '{}'", root);
+
+    let files = partition_files(Path::new(&root))?;
+    if files.is_empty() {
+        return Err(errors::Error {
+            message: format!("No parquet files found at {}!", root),
+        });
+    }
+
+    read_parquet_files(files, options)
+}
+
+/// Reads the files of a dataset, checking the columns read from every file have the same types.
+fn read_parquet_files<D: Clone + DeserializeOwned + Serialize + Send>(
+    paths: Vec<(PathBuf, Partition)>,
+    options: &ParquetReadOptions,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    // Files are opened only to read their metadata here, then one at a time while reading their rows,
+    // so a dataset can have more files than the limit of open files.
+    let mut files = Vec::new();
+    let mut expected: Option<(PathBuf, Vec<ColumnSignature>)> = None;
+    for (file_path, partition) in paths {
+        let reader = open_parquet(open_file(&file_path)?)?;
+        let projection = parquet_projection::<D, File>(&reader, options, &[])?;
+
+        // Every file must have the same types for the columns that are read.
+        let columns = match &projection {
+            Some(p) => column_signatures(p),
+            None => column_signatures(reader.metadata().file_metadata().schema()),
+        };
+        match &expected {
+            Some((first_path, expected)) if *expected != columns => {
+                return Err(errors::Error {
+                    message: format!(
+                        "Schema of {} is not compatible with {}!",
+                        file_path.display(),
+                        first_path.display()
+                    ),
+                });
+            }
+            Some(_) => {}
+            None => expected = Some((file_path.clone(), columns)),
+        }
+
+        files.push((file_path, projection, partition));
+    }
+
+    let read = |(file_path, projection, partition): &(PathBuf, Option<Type>, Partition)| {
+        let reader = open_parquet(open_file(file_path)?)?;
+        read_parquet_rows::<D, File>(&reader, projection.clone(), &[], partition)
+    };
+
+    let results: Vec<Result<Vec<D>, errors::Error>> = if options.parallel && files.len() > 1 {
+        let threads = match std::thread::available_parallelism() {
+            Ok(n) => n.get(),
+            Err(_) => 1,
+        };
+        let per_thread = files.len().div_ceil(threads);
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = files
+                .chunks(per_thread)
+                .map(|group| scope.spawn(move || group.iter().map(read).collect::<Vec<_>>()))
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|h| match h.join() {
+                    Ok(results) => results,
+                    Err(e) => std::panic::resume_unwind(e),
+                })
+                .collect()
+        })
+    } else {
+        files.iter().map(read).collect()
+    };
+
+    // Rows are moved from each file into the DataFrame, without cloning them.
+    let mut parts = Vec::with_capacity(results.len());
+    for result in results {
+        parts.push(result?);
+    }
+    let mut data = Vec::with_capacity(parts.iter().map(|p| p.len()).sum());
+    for mut part in parts {
+        data.append(&mut part);
+    }
+
+    Ok(DataFrame::new(data))
+}
+
+fn open_file(path: &Path) -> Result<File, errors::Error> {
+    match File::open(path) {
+        Ok(f) => Ok(f),
+        Err(_) => Err(errors::Error {
+            message: format!("Could not open file {}", path.display()),
+        }),
+    }
+}
+
+/// Path and types of a leaf column of a parquet schema.
+/// Required and optional columns have the same signature, both are read into the same fields.
+#[derive(Debug, PartialEq)]
+struct ColumnSignature {
+    path: String,
+    repetition: i16,
+    physical_type: PhysicalType,
+    logical_type: Option<LogicalType>,
+    converted_type: ConvertedType,
+    length: i32,
+    precision: i32,
+    scale: i32,
+}
+
+/// Signatures of the leaf columns of a schema sorted by path, so the order of the columns does not matter.
+fn column_signatures(schema: &Type) -> Vec<ColumnSignature> {
+    let descriptor = SchemaDescriptor::new(Arc::new(schema.clone()));
+    let mut columns: Vec<ColumnSignature> = descriptor
+        .columns()
+        .iter()
+        .map(|c| ColumnSignature {
+            path: c.path().string(),
+            repetition: c.max_rep_level(),
+            physical_type: c.physical_type(),
+            logical_type: c.logical_type(),
+            converted_type: c.converted_type(),
+            length: c.type_length(),
+            precision: c.type_precision(),
+            scale: c.type_scale(),
+        })
+        .collect();
+    columns.sort_by(|a, b| a.path.cmp(&b.path));
+    columns
+}
+
+/// Paths of the parquet files of a dataset, sorted by name.
+fn dataset_paths(path: &str) -> Result<Vec<PathBuf>, errors::Error> {
+    let mut paths = Vec::new();
+
+    if Path::new(path).is_dir() {
+        let entries = match std::fs::read_dir(path) {
+            Ok(e) => e,
+            Err(e) => {
+                return Err(errors::Error {
+                    message: e.to_string(),
+                })
+            }
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_file()
+                && name.ends_with(".parquet")
+                && !name.starts_with('_')
+                && !name.starts_with('.')
+            {
+                paths.push(entry.path());
+            }
+        }
+    } else {
+        let entries = match glob::glob(path) {
+            Ok(e) => e,
+            Err(e) => {
+                return Err(errors::Error {
+                    message: e.to_string(),
+                })
+            }
+        };
+
+        for entry in entries.flatten() {
+            if entry.is_file() {
+                paths.push(entry);
+            }
+        }
+    }
+
+    if paths.is_empty() {
+        return Err(errors::Error {
+            message: format!("No parquet files found at {}!", path),
+        });
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Read an Apache Parquet from any source of chunks (files, in-memory bytes...).
+/// The source is read on demand: the metadata first, then only the column chunks to decode.
+/// Use [function@read_parquet_from_bytes] for content already in memory.
+/// Example:
+/// ```
+/// use combee::read_parquet_from_reader;
+/// use serde::{Serialize, Deserialize};
+/// use std::fs::File;
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: u32
+/// }
+///
+/// let file = File::open("tests/fixtures/basic.parquet").unwrap();
+/// let df = read_parquet_from_reader::<D, _>(file).unwrap();
+/// ```
+pub fn read_parquet_from_reader<
+    D: Clone + DeserializeOwned + Serialize,
+    R: 'static + ChunkReader,
+>(
+    reader: R,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    read_parquet_chunks(reader, &ParquetReadOptions::default(), &[])
+}
+
+/// Read an Apache Parquet from in-memory content, e.g. a buffer filled by [dataframe::DataFrame::write_parquet].
+/// Example:
+/// ```
+/// use combee::read_parquet_from_bytes;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Clone, Serialize, Deserialize)]
+/// struct D {
+///     name: String,
+///     age: u32
+/// }
+///
+/// let content = std::fs::read("tests/fixtures/basic.parquet").unwrap();
+/// let df = read_parquet_from_bytes::<D, _>(content).unwrap();
+/// ```
+pub fn read_parquet_from_bytes<D: Clone + DeserializeOwned + Serialize, B: Into<Bytes>>(
+    bytes: B,
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    read_parquet_from_reader(bytes.into())
+}
+
+fn read_parquet_chunks<D: Clone + DeserializeOwned + Serialize, R: 'static + ChunkReader>(
+    chunks: R,
+    options: &ParquetReadOptions,
+    predicates: &[Predicate],
+) -> Result<dataframe::DataFrame<D>, errors::Error> {
+    let reader = open_parquet(chunks)?;
+    let projection = parquet_projection::<D, R>(&reader, options, predicates)?;
+    let data = read_parquet_rows(&reader, projection, predicates, &Partition::new())?;
+
+    Ok(DataFrame::new(data))
+}
+
+fn open_parquet<R: 'static + ChunkReader>(
+    chunks: R,
+) -> Result<SerializedFileReader<R>, errors::Error> {
+    match SerializedFileReader::new(chunks) {
+        Ok(r) => Ok(r),
+        Err(e) => Err(errors::Error {
+            message: e.to_string(),
+        }),
+    }
+}
+
+/// Columns of the parquet decoded for the struct D, None when every column is decoded.
+fn parquet_projection<D: DeserializeOwned, R: 'static + ChunkReader>(
+    reader: &SerializedFileReader<R>,
+    options: &ParquetReadOptions,
+    predicates: &[Predicate],
+) -> Result<Option<Type>, errors::Error> {
+    // The columns of the predicates are always read, so they can be checked for each row.
+    let predicate_columns: Vec<String> =
+        predicates.iter().map(|p| p.column().to_string()).collect();
+
+    let root = reader.metadata().file_metadata().schema();
+    // A predicate on a missing column would silently filter every row out.
+    for predicate in predicates {
+        match root
+            .get_fields()
+            .iter()
+            .find(|f| f.name() == predicate.column())
+        {
+            Some(field) => predicate.check(field)?,
+            None => {
+                return Err(errors::Error {
+                    message: format!(
+                        "Column {} of the predicate not found in parquet!",
+                        predicate.column()
+                    ),
+                })
+            }
+        }
+    }
+
+    match &options.columns {
+        Some(columns) => {
+            let mut columns = columns.clone();
+            for column in predicate_columns.iter() {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
+            }
+            Ok(Some(projection::project(root, &columns)?))
+        }
+        None => Ok(projection::project_struct::<D>(root, &predicate_columns)),
+    }
+}
+
+fn read_parquet_rows<D: DeserializeOwned, R: 'static + ChunkReader>(
+    reader: &SerializedFileReader<R>,
+    projection: Option<Type>,
+    predicates: &[Predicate],
+    partition: &Partition,
+) -> Result<Vec<D>, errors::Error> {
+    let mut data = Vec::new();
+    for i in 0..reader.num_row_groups() {
+        let row_group = match reader.get_row_group(i) {
             Ok(r) => r,
             Err(e) => {
                 return Err(errors::Error {
@@ -134,13 +991,32 @@ pub fn read_parquet<D: Clone + DeserializeOwned + Serialize>(
             }
         };
 
-        let mut data = Vec::new();
-        for r in reader.into_iter() {
+        if !predicates.iter().all(|p| p.may_match(row_group.metadata())) {
+            log::debug!("Skipping row group {} using its statistics", i);
+            continue;
+        }
+
+        let rows = match RowIter::from_row_group(projection.clone(), row_group.as_ref()) {
+            Ok(rows) => rows,
+            Err(e) => {
+                return Err(errors::Error {
+                    message: e.to_string(),
+                })
+            }
+        };
+
+        for r in rows {
             match r {
-                Ok(row) => match from_row(&row) {
-                    Ok(d) => data.push(d),
-                    Err(e) => return Err(e),
-                },
+                Ok(row) => {
+                    if !predicates.iter().all(|p| p.matches(&row)) {
+                        continue;
+                    }
+
+                    match from_row_with_partitions(&row, partition) {
+                        Ok(d) => data.push(d),
+                        Err(e) => return Err(e),
+                    }
+                }
                 Err(e) => {
                     return Err(errors::Error {
                         message: e.to_string(),
@@ -148,13 +1024,9 @@ pub fn read_parquet<D: Clone + DeserializeOwned + Serialize>(
                 }
             }
         }
-
-        return Ok(DataFrame::new(data));
-    } else {
-        return Err(errors::Error {
-            message: format!("Could not open file {}", path),
-        });
     }
+
+    Ok(data)
 }
 
 /// Returns a list of string with the columns of a given CSV.
@@ -166,32 +1038,41 @@ pub fn read_parquet<D: Clone + DeserializeOwned + Serialize>(
 /// println!("{}", columns.join(","));
 /// ```
 pub fn read_csv_schema(path: String) -> Result<Vec<String>, errors::Error> {
-    let file = match File::open(&path) {
-        Ok(f) => f,
-        Err(_) => {
-            return Err(errors::Error {
-                message: format!("Could not open CSV file at path {}!", path),
-            })
-        }
-    };
+    read_csv_schema_with(path, &CsvReadOptions::default())
+}
 
-    let mut buffer = BufReader::new(file);
-    let mut first_line = String::new();
+/// Returns a list of string with the columns of a given CSV read with custom options.
+/// If the CSV has no header the columns are named by their position ("0", "1", ...).
+/// Example:
+/// ```no_run
+/// use combee::{read_csv_schema_with, options::CsvReadOptions};
+///
+/// let options = CsvReadOptions::new().delimiter(b'\t');
+/// let columns = read_csv_schema_with("dataset.tsv".to_string(), &options).unwrap();
+/// println!("{}", columns.join(","));
+/// ```
+pub fn read_csv_schema_with(
+    path: String,
+    options: &CsvReadOptions,
+) -> Result<Vec<String>, errors::Error> {
+    let mut reader = csv_reader(open_csv_file(&path)?, options)?;
 
-    match buffer.read_line(&mut first_line) {
-        Ok(_) => (),
-        Err(_) => {
-            return Err(errors::Error {
+    if options.has_headers {
+        match reader.headers() {
+            Ok(headers) => Ok(headers.iter().map(String::from).collect()),
+            Err(_) => Err(errors::Error {
                 message: format!("Could not read first line of CSV file at path {}", path),
-            })
+            }),
         }
-    };
-
-    Ok(first_line
-        .trim()
-        .split(",")
-        .map(|x| String::from(x))
-        .collect())
+    } else {
+        let mut record = csv::StringRecord::new();
+        match reader.read_record(&mut record) {
+            Ok(_) => Ok((0..record.len()).map(|i| i.to_string()).collect()),
+            Err(_) => Err(errors::Error {
+                message: format!("Could not read first line of CSV file at path {}", path),
+            }),
+        }
+    }
 }
 
 /// Returns the schema of a parquet as a string.
@@ -0,0 +1,498 @@
+use arrow2::io::{
+    ipc,
+    parquet::write::{CompressionOptions, GzipLevel, Version, WriteOptions, ZstdLevel},
+};
+
+use crate::errors;
+
+/// Options used to read CSV files.
+/// Example:
+/// ```
+/// use combee::options::CsvReadOptions;
+///
+/// let options = CsvReadOptions::new()
+///     .delimiter(b';')
+///     .has_headers(false)
+///     .comment(Some(b'#'))
+///     .skip_rows(2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CsvReadOptions {
+    pub(crate) delimiter: u8,
+    pub(crate) quote: u8,
+    pub(crate) escape: Option<u8>,
+    pub(crate) has_headers: bool,
+    pub(crate) comment: Option<u8>,
+    pub(crate) trim: bool,
+    pub(crate) flexible: bool,
+    pub(crate) skip_rows: usize,
+}
+
+impl CsvReadOptions {
+    /// Instantiate the default options: comma separated, double quoted fields and a header row.
+    pub fn new() -> Self {
+        CsvReadOptions {
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            has_headers: true,
+            comment: None,
+            trim: false,
+            flexible: false,
+            skip_rows: 0,
+        }
+    }
+
+    /// Set the field delimiter (default: `b','`).
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the quote character (default: `b'"'`).
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set the escape character used inside quoted fields, e.g. `Some(b'\\')` (default: `None`).
+    pub fn escape(mut self, escape: Option<u8>) -> Self {
+        self.escape = escape;
+        self
+    }
+
+    /// Whether the first row is a header (default: `true`).
+    /// Without a header the columns are matched to the struct fields by position.
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Lines starting with this character are ignored (default: `None`).
+    pub fn comment(mut self, comment: Option<u8>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Trim leading and trailing whitespace of headers and fields (default: `false`).
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Allow rows with a different number of fields (default: `false`).
+    pub fn flexible(mut self, flexible: bool) -> Self {
+        self.flexible = flexible;
+        self
+    }
+
+    /// Number of lines to skip at the beginning of the file, before the header (default: `0`).
+    pub fn skip_rows(mut self, skip_rows: usize) -> Self {
+        self.skip_rows = skip_rows;
+        self
+    }
+
+    pub(crate) fn reader_builder(&self) -> csv::ReaderBuilder {
+        let mut builder = csv::ReaderBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .escape(self.escape)
+            .has_headers(self.has_headers)
+            .comment(self.comment)
+            .flexible(self.flexible)
+            .trim(if self.trim {
+                csv::Trim::All
+            } else {
+                csv::Trim::None
+            });
+        builder
+    }
+}
+
+impl Default for CsvReadOptions {
+    fn default() -> Self {
+        CsvReadOptions::new()
+    }
+}
+
+/// Quoting strategy used when writing CSV files.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QuoteStyle {
+    /// Quote every field.
+    Always,
+    /// Quote fields only when they contain a quote, a delimiter or a line terminator.
+    Necessary,
+    /// Quote every field that is not a number.
+    NonNumeric,
+    /// Never quote fields, even if it produces an invalid CSV.
+    Never,
+}
+
+/// Line terminator used when writing CSV files.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineTerminator {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    Crlf,
+    /// Any single byte.
+    Any(u8),
+}
+
+/// Options used to write CSV files.
+/// Example:
+/// ```
+/// use combee::options::{CsvWriteOptions, QuoteStyle};
+///
+/// let options = CsvWriteOptions::new()
+///     .delimiter(b'\t')
+///     .quote_style(QuoteStyle::Never)
+///     .float_precision(Some(2))
+///     .append(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CsvWriteOptions {
+    pub(crate) delimiter: u8,
+    pub(crate) quote_style: QuoteStyle,
+    pub(crate) line_terminator: LineTerminator,
+    pub(crate) has_headers: bool,
+    pub(crate) float_precision: Option<usize>,
+    pub(crate) null_value: String,
+    pub(crate) append: bool,
+}
+
+impl CsvWriteOptions {
+    /// Instantiate the default options: comma separated, quoted only when necessary and with a header row.
+    pub fn new() -> Self {
+        CsvWriteOptions {
+            delimiter: b',',
+            quote_style: QuoteStyle::Necessary,
+            line_terminator: LineTerminator::Lf,
+            has_headers: true,
+            float_precision: None,
+            null_value: String::new(),
+            append: false,
+        }
+    }
+
+    /// Set the field delimiter (default: `b','`).
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the quoting strategy (default: [QuoteStyle::Necessary]).
+    pub fn quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+
+    /// Set the line terminator (default: [LineTerminator::Lf]).
+    pub fn line_terminator(mut self, line_terminator: LineTerminator) -> Self {
+        self.line_terminator = line_terminator;
+        self
+    }
+
+    /// Whether to write the header row (default: `true`).
+    /// When appending to a non empty file the header is never written.
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Number of decimal places used to write `f32` and `f64` values (default: `None`, shortest representation).
+    pub fn float_precision(mut self, float_precision: Option<usize>) -> Self {
+        self.float_precision = float_precision;
+        self
+    }
+
+    /// Text written for `None` values (default: empty string).
+    pub fn null_value(mut self, null_value: &str) -> Self {
+        self.null_value = null_value.to_string();
+        self
+    }
+
+    /// Append the rows to the end of the file instead of truncating it (default: `false`).
+    pub fn append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+
+    pub(crate) fn writer_builder(&self) -> csv::WriterBuilder {
+        let mut builder = csv::WriterBuilder::new();
+        builder
+            .delimiter(self.delimiter)
+            .has_headers(self.has_headers)
+            .quote_style(match self.quote_style {
+                QuoteStyle::Always => csv::QuoteStyle::Always,
+                QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+                QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+                QuoteStyle::Never => csv::QuoteStyle::Never,
+            })
+            .terminator(match self.line_terminator {
+                LineTerminator::Lf => csv::Terminator::Any(b'\n'),
+                LineTerminator::Crlf => csv::Terminator::CRLF,
+                LineTerminator::Any(b) => csv::Terminator::Any(b),
+            });
+        builder
+    }
+}
+
+impl Default for CsvWriteOptions {
+    fn default() -> Self {
+        CsvWriteOptions::new()
+    }
+}
+
+/// Compression codec used when writing Parquet files.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParquetCompression {
+    /// No compression.
+    Uncompressed,
+    /// Snappy compression.
+    Snappy,
+    /// Gzip compression with an optional level (0 to 10).
+    Gzip(Option<u8>),
+    /// LZ4 compression (LZ4_RAW codec).
+    Lz4,
+    /// Zstandard compression with an optional level (1 to 22).
+    Zstd(Option<i32>),
+}
+
+/// Options used to write Parquet files.
+/// Example:
+/// ```
+/// use combee::options::{ParquetCompression, ParquetWriteOptions};
+///
+/// let options = ParquetWriteOptions::new()
+///     .compression(ParquetCompression::Zstd(Some(3)))
+///     .row_group_size(Some(100_000))
+///     .dictionary_encoding(true)
+///     .decimal("price", 10, 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParquetWriteOptions {
+    pub(crate) compression: ParquetCompression,
+    pub(crate) statistics: bool,
+    pub(crate) row_group_size: Option<usize>,
+    pub(crate) dictionary_encoding: bool,
+    pub(crate) data_page_size: Option<usize>,
+    pub(crate) decimals: Vec<(String, usize, usize)>,
+}
+
+impl ParquetWriteOptions {
+    /// Instantiate the default options: uncompressed, with statistics and a single row group.
+    pub fn new() -> Self {
+        ParquetWriteOptions {
+            compression: ParquetCompression::Uncompressed,
+            statistics: true,
+            row_group_size: None,
+            dictionary_encoding: false,
+            data_page_size: None,
+            decimals: Vec::new(),
+        }
+    }
+
+    /// Set the compression codec (default: [ParquetCompression::Uncompressed]).
+    pub fn compression(mut self, compression: ParquetCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Whether to write min/max statistics of each column chunk (default: `true`).
+    pub fn statistics(mut self, statistics: bool) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
+    /// Maximum number of rows in each row group (default: `None`, all the rows in a single row group).
+    pub fn row_group_size(mut self, row_group_size: Option<usize>) -> Self {
+        self.row_group_size = row_group_size;
+        self
+    }
+
+    /// Use dictionary encoding for string columns (default: `false`).
+    pub fn dictionary_encoding(mut self, dictionary_encoding: bool) -> Self {
+        self.dictionary_encoding = dictionary_encoding;
+        self
+    }
+
+    /// Maximum size of a data page in bytes (default: `None`, 1MB).
+    pub fn data_page_size(mut self, data_page_size: Option<usize>) -> Self {
+        self.data_page_size = data_page_size;
+        self
+    }
+
+    /// Write a top level column as DECIMAL(precision, scale), e.g. `decimal("price", 10, 2)`.
+    /// The column can be a [Decimal](crate::decimal::Decimal), a string, a float or an integer field;
+    /// values with more decimal places than the scale are rounded half away from zero.
+    /// [Decimal](crate::decimal::Decimal) fields are written as DECIMAL without this option too,
+    /// with the smallest precision and scale that fit their values.
+    pub fn decimal(mut self, column: &str, precision: usize, scale: usize) -> Self {
+        self.decimals.push((column.to_string(), precision, scale));
+        self
+    }
+
+    pub(crate) fn write_options(&self) -> Result<WriteOptions, errors::Error> {
+        let compression = match self.compression {
+            ParquetCompression::Uncompressed => CompressionOptions::Uncompressed,
+            ParquetCompression::Snappy => CompressionOptions::Snappy,
+            ParquetCompression::Gzip(level) => match level.map(GzipLevel::try_new).transpose() {
+                Ok(level) => CompressionOptions::Gzip(level),
+                Err(e) => {
+                    return Err(errors::Error {
+                        message: e.to_string(),
+                    })
+                }
+            },
+            ParquetCompression::Lz4 => CompressionOptions::Lz4Raw,
+            ParquetCompression::Zstd(level) => match level.map(ZstdLevel::try_new).transpose() {
+                Ok(level) => CompressionOptions::Zstd(level),
+                Err(e) => {
+                    return Err(errors::Error {
+                        message: e.to_string(),
+                    })
+                }
+            },
+        };
+
+        Ok(WriteOptions {
+            write_statistics: self.statistics,
+            compression,
+            version: Version::V2,
+            data_pagesize_limit: self.data_page_size,
+        })
+    }
+}
+
+impl Default for ParquetWriteOptions {
+    fn default() -> Self {
+        ParquetWriteOptions::new()
+    }
+}
+
+/// Options used to read Parquet files.
+/// By default only the columns used by the fields of the struct are decoded.
+/// Example:
+/// ```
+/// use combee::options::ParquetReadOptions;
+///
+/// let options = ParquetReadOptions::new().columns(&["name", "age"]).parallel(true);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ParquetReadOptions {
+    pub(crate) columns: Option<Vec<String>>,
+    pub(crate) parallel: bool,
+}
+
+impl ParquetReadOptions {
+    /// Instantiate the default options: decode only the columns needed by the struct.
+    pub fn new() -> Self {
+        ParquetReadOptions {
+            columns: None,
+            parallel: false,
+        }
+    }
+
+    /// Decode only the given top level columns.
+    pub fn columns(mut self, columns: &[&str]) -> Self {
+        self.columns = Some(columns.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Read the files of a dataset in parallel, one thread per available core (default: `false`).
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+}
+
+impl Default for ParquetReadOptions {
+    fn default() -> Self {
+        ParquetReadOptions::new()
+    }
+}
+
+/// Layout of Arrow IPC data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IpcFormat {
+    /// Random access file format (Feather v2), with a footer indexing the record batches.
+    File,
+    /// Streaming format, record batches are written one after another and read sequentially.
+    Stream,
+}
+
+/// Compression codec of the buffers of Arrow IPC record batches.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IpcCompression {
+    /// No compression.
+    Uncompressed,
+    /// LZ4 frame compression.
+    Lz4,
+    /// Zstandard compression.
+    Zstd,
+}
+
+/// Options used to write Arrow IPC files.
+/// Example:
+/// ```
+/// use combee::options::{IpcCompression, IpcFormat, IpcWriteOptions};
+///
+/// let options = IpcWriteOptions::new()
+///     .format(IpcFormat::Stream)
+///     .compression(IpcCompression::Lz4)
+///     .batch_size(Some(100_000));
+/// ```
+#[derive(Clone, Debug)]
+pub struct IpcWriteOptions {
+    pub(crate) format: IpcFormat,
+    pub(crate) compression: IpcCompression,
+    pub(crate) batch_size: Option<usize>,
+}
+
+impl IpcWriteOptions {
+    /// Instantiate the default options: uncompressed file format with a single record batch.
+    pub fn new() -> Self {
+        IpcWriteOptions {
+            format: IpcFormat::File,
+            compression: IpcCompression::Uncompressed,
+            batch_size: None,
+        }
+    }
+
+    /// Set the layout of the data (default: [IpcFormat::File]).
+    pub fn format(mut self, format: IpcFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the compression codec (default: [IpcCompression::Uncompressed]).
+    pub fn compression(mut self, compression: IpcCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Maximum number of rows in each record batch (default: `None`, all the rows in a single batch).
+    /// Writing returns an error if it is zero.
+    pub fn batch_size(mut self, batch_size: Option<usize>) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub(crate) fn write_options(&self) -> ipc::write::WriteOptions {
+        ipc::write::WriteOptions {
+            compression: match self.compression {
+                IpcCompression::Uncompressed => None,
+                IpcCompression::Lz4 => Some(ipc::write::Compression::LZ4),
+                IpcCompression::Zstd => Some(ipc::write::Compression::ZSTD),
+            },
+        }
+    }
+}
+
+impl Default for IpcWriteOptions {
+    fn default() -> Self {
+        IpcWriteOptions::new()
+    }
+}
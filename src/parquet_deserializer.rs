@@ -1,8 +1,12 @@
 use parquet::record::{Field, Row};
-use serde::de::{self, Deserialize, Visitor};
+use serde::de::{self, value::SeqDeserializer, Deserialize, Visitor};
 use serde::forward_to_deserialize_any;
 
-use crate::errors::Error;
+use crate::{
+    decimal::Decimal,
+    errors::Error,
+    temporal::{self, Temporal},
+};
 
 type KeyVal<'de> = (Value<'de>, Value<'de>);
 
@@ -11,6 +15,7 @@ enum Value<'de> {
     Name(&'de str),
     Field(&'de Field),
     Seq(Vec<KeyVal<'de>>),
+    Partition(Option<&'de str>),
 }
 
 struct MapAccess<'a, 'de: 'a> {
@@ -63,12 +68,12 @@ impl<'de, 'a> de::MapAccess<'de> for MapAccess<'a, 'de> {
 
 struct SeqAccess<'a, 'de: 'a> {
     de: &'a mut Deserializer<'de>,
-    iter: std::slice::Iter<'de, Field>,
+    iter: std::vec::IntoIter<&'de Field>,
     len: usize,
 }
 
 impl<'a, 'de: 'a> SeqAccess<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>, v: &'de [Field]) -> Self {
+    fn new(de: &'a mut Deserializer<'de>, v: Vec<&'de Field>) -> Self {
         let len = v.len();
         SeqAccess {
             de,
@@ -101,14 +106,18 @@ impl<'de, 'a> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
 
 pub struct Deserializer<'de> {
     input: &'de Row,
+    partitions: &'de [(String, Option<String>)],
     value: Option<Value<'de>>,
 }
 
 impl<'de> Deserializer<'de> {
-    pub fn from_row(r: &'de Row) -> Self {
+    pub fn from_row_with_partitions(
+        r: &'de Row,
+        partitions: &'de [(String, Option<String>)],
+    ) -> Self {
         Deserializer {
             input: r,
-
+            partitions,
             value: None,
         }
     }
@@ -117,11 +126,18 @@ impl<'de> Deserializer<'de> {
         match self.value.take() {
             Some(v) => Ok(v),
             None => {
-                let map = self
+                let mut map = self
                     .input
                     .get_column_iter()
                     .map(|(n, f)| (Value::Name(n), Value::Field(f)))
                     .collect::<Vec<_>>();
+
+                // Partition values taken from the directories are added as extra columns.
+                for (name, value) in self.partitions {
+                    if !map.iter().any(|(n, _)| *n == Value::Name(name)) {
+                        map.push((Value::Name(name), Value::Partition(value.as_deref())));
+                    }
+                }
                 let value = Value::Seq(map);
                 Ok(value)
             }
@@ -129,15 +145,44 @@ impl<'de> Deserializer<'de> {
     }
 }
 
-pub fn from_row<'a, T>(r: &'a Row) -> Result<T, Error>
+pub fn from_row_with_partitions<'a, T>(
+    r: &'a Row,
+    partitions: &'a [(String, Option<String>)],
+) -> Result<T, Error>
 where
     T: Deserialize<'a>,
 {
-    let mut deserializer = Deserializer::from_row(r);
+    let mut deserializer = Deserializer::from_row_with_partitions(r, partitions);
     let t = T::deserialize(&mut deserializer)?;
     Ok(t)
 }
 
+/// Partition values are strings, they are parsed when the struct expects another type.
+macro_rules! deserialize_partition {
+    ($($method:ident => $visit:ident),*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                let value = self.get_next_value()?;
+                match value {
+                    Value::Partition(Some(s)) => match s.parse() {
+                        Ok(v) => visitor.$visit(v),
+                        Err(_) => Err(Error {
+                            message: format!("Invalid partition value '{}'!", s),
+                        }),
+                    },
+                    _ => {
+                        self.value = Some(value);
+                        self.deserialize_any(visitor)
+                    }
+                }
+            }
+        )*
+    };
+}
+
 impl<'de: 'a, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     type Error = Error;
 
@@ -149,6 +194,8 @@ impl<'de: 'a, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         match value {
             Value::Seq(v) => visitor.visit_map(MapAccess::new(&mut self, v)),
             Value::Name(s) => visitor.visit_str(s),
+            Value::Partition(Some(s)) => visitor.visit_str(s),
+            Value::Partition(None) => visitor.visit_unit(),
             Value::Field(f) => match f {
                 Field::Null => visitor.visit_unit(),
                 Field::Bool(v) => visitor.visit_bool(*v),
@@ -162,7 +209,7 @@ impl<'de: 'a, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
                 Field::ULong(v) => visitor.visit_u64(*v),
                 Field::Float(v) => visitor.visit_f32(*v),
                 Field::Double(v) => visitor.visit_f64(*v),
-                Field::Decimal(_) => todo!(),
+                Field::Decimal(v) => visitor.visit_string(Decimal::from_parquet(v)?.to_string()),
                 Field::Str(v) => visitor.visit_str(v),
                 Field::Bytes(v) => visitor.visit_bytes(v.data()),
                 Field::Date(v) => visitor.visit_u32(*v as u32),
@@ -182,15 +229,126 @@ impl<'de: 'a, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
                         .collect(),
                 )),
                 Field::ListInternal(v) => {
-                    visitor.visit_seq(SeqAccess::new(&mut self, v.elements()))
+                    visitor.visit_seq(SeqAccess::new(&mut self, v.elements().iter().collect()))
                 }
             },
         }
     }
 
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Decimals are visited as strings by default, floats read them as numbers instead.
+        let value = self.get_next_value()?;
+        match value {
+            Value::Partition(Some(s)) => match s.parse() {
+                Ok(v) => visitor.visit_f64(v),
+                Err(_) => Err(Error {
+                    message: format!("Invalid partition value '{}'!", s),
+                }),
+            },
+            Value::Field(Field::Decimal(v)) => {
+                visitor.visit_f64(Decimal::from_parquet(v)?.to_f64())
+            }
+            _ => {
+                self.value = Some(value);
+                self.deserialize_any(visitor)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let value = self.get_next_value()?;
+        match value {
+            Value::Field(Field::Null) | Value::Partition(None) => visitor.visit_none(),
+            _ => {
+                self.value = Some(value);
+                visitor.visit_some(self)
+            }
+        }
+    }
+
+    deserialize_partition! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Tuples are written as groups with a column for each element.
+        let value = self.get_next_value()?;
+        match value {
+            Value::Field(Field::Group(v)) => visitor.visit_seq(SeqAccess::new(
+                self,
+                v.get_column_iter().map(|(_, f)| f).collect(),
+            )),
+            _ => {
+                self.value = Some(value);
+                self.deserialize_any(visitor)
+            }
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // Fields marked by the helpers of the temporal module are deserialized from tuples of their components.
+        let temporal = match Temporal::from_marker(name) {
+            Some(t) => t,
+            None => return self.deserialize_any(visitor),
+        };
+
+        let value = self.get_next_value()?;
+        let components = match value {
+            Value::Field(Field::Date(v)) => {
+                temporal::components(temporal::days_to_nanos(*v), temporal.len())
+            }
+            Value::Field(Field::TimestampMillis(v)) => {
+                temporal::components(*v as i128 * 1_000_000, temporal.len())
+            }
+            Value::Field(Field::TimestampMicros(v)) => {
+                temporal::components(*v as i128 * 1_000, temporal.len())
+            }
+            _ => None,
+        };
+
+        match components {
+            Some(components) => visitor
+                .visit_newtype_struct(SeqDeserializer::<_, Error>::new(components?.into_iter())),
+            None => {
+                self.value = Some(value);
+                self.deserialize_tuple(temporal.len(), visitor)
+            }
+        }
+    }
+
     forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit seq
-            bytes byte_buf map tuple_struct struct identifier
-            tuple ignored_any unit_struct enum option newtype_struct
+        char str string unit seq bytes byte_buf map tuple_struct struct identifier
+            ignored_any unit_struct enum
     }
 }
@@ -0,0 +1,177 @@
+use serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use serde_json::Value;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::errors;
+
+/// Directory name used for null partition values, as in Hive.
+const DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Columns and values of a partition, None for null values.
+pub type Partition = Vec<(String, Option<String>)>;
+
+/// Columns and values of a partition key, the key must be a struct of scalar fields.
+/// Empty strings are kept apart from null values, they are written as an empty directory value (`column=`).
+pub fn partition_values<K: Serialize>(key: &K) -> Result<Partition, errors::Error> {
+    let to_error = |e: serde_json::Error| errors::Error {
+        message: e.to_string(),
+    };
+
+    // The key is written as a JSON object, then its fields are read in order.
+    let json = serde_json::to_string(key).map_err(to_error)?;
+    let fields = match serde_json::from_str::<KeyFields>(&json) {
+        Ok(fields) => fields.0,
+        Err(_) => {
+            return Err(errors::Error {
+                message: "Partition key must be a struct!".to_string(),
+            })
+        }
+    };
+
+    fields
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::Null => None,
+                Value::String(s) => Some(s),
+                Value::Bool(b) => Some(b.to_string()),
+                Value::Number(n) => Some(n.to_string()),
+                Value::Array(_) | Value::Object(_) => {
+                    return Err(errors::Error {
+                        message: format!("Partition column {} must be a scalar!", name),
+                    })
+                }
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Fields of a JSON object in the order they are written.
+struct KeyFields(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for KeyFields {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldsVisitor;
+
+        impl<'de> Visitor<'de> for FieldsVisitor {
+            type Value = KeyFields;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a struct")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<KeyFields, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(field) = map.next_entry()? {
+                    fields.push(field);
+                }
+                Ok(KeyFields(fields))
+            }
+        }
+
+        deserializer.deserialize_map(FieldsVisitor)
+    }
+}
+
+/// Relative directory of a partition, e.g. `year=2023/month=1`.
+pub fn partition_dir(partition: &Partition) -> PathBuf {
+    partition
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Some(v) => escape(v),
+                None => DEFAULT_PARTITION.to_string(),
+            };
+            format!("{}={}", escape(name), value)
+        })
+        .collect()
+}
+
+/// Parquet files under the root directory with the partition values of their directories.
+pub fn partition_files(root: &Path) -> Result<Vec<(PathBuf, Partition)>, errors::Error> {
+    let mut files = Vec::new();
+    let mut dirs = vec![(root.to_path_buf(), Partition::new())];
+
+    while let Some((dir, partition)) = dirs.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(e) => {
+                return Err(errors::Error {
+                    message: format!("Could not read directory {}: {}", dir.display(), e),
+                })
+            }
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('_') || name.starts_with('.') {
+                continue;
+            }
+
+            if path.is_dir() {
+                // Directories without `=` are not partitions, e.g. a nested dataset folder.
+                let mut partition = partition.clone();
+                if let Some((column, value)) = name.split_once('=') {
+                    let value = if value == DEFAULT_PARTITION {
+                        None
+                    } else {
+                        Some(unescape(value))
+                    };
+                    partition.push((unescape(column), value));
+                }
+                dirs.push((path, partition));
+            } else if name.ends_with(".parquet") {
+                files.push((path, partition.clone()));
+            }
+        }
+    }
+
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+/// Percent-encodes the characters not allowed in a partition directory name.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_control() || "\"#%'*/:=?\\{}[]^".contains(c) {
+            for b in c.to_string().bytes() {
+                escaped.push_str(&format!("%{:02X}", b));
+            }
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                unescaped.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                unescaped.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&unescaped).to_string()
+}
@@ -0,0 +1,449 @@
+use parquet::{
+    basic::{ConvertedType, LogicalType, Type as PhysicalType},
+    file::{metadata::RowGroupMetaData, statistics::Statistics},
+    record::{Field, Row},
+    schema::types::Type,
+};
+use std::{cmp::Ordering, ops::Bound};
+
+use crate::{
+    decimal::{parquet_scale, Decimal},
+    errors,
+};
+
+/// A scalar value compared against a column of a parquet file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Scalar {
+    /// Boolean value.
+    Bool(bool),
+    /// Integer value, dates are the number of days since epoch and timestamps the raw number of millis/micros.
+    Int(i64),
+    /// Floating point value.
+    Float(f64),
+    /// String value.
+    Str(String),
+    /// Fixed point value, compared exactly with DECIMAL columns.
+    Decimal(Decimal),
+}
+
+impl Scalar {
+    fn compare(&self, other: &Scalar) -> Option<Ordering> {
+        match (self, other) {
+            (Scalar::Bool(a), Scalar::Bool(b)) => a.partial_cmp(b),
+            (Scalar::Int(a), Scalar::Int(b)) => a.partial_cmp(b),
+            (Scalar::Float(a), Scalar::Float(b)) => a.partial_cmp(b),
+            (Scalar::Int(a), Scalar::Float(b)) => (*a as f64).partial_cmp(b),
+            (Scalar::Float(a), Scalar::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Scalar::Str(a), Scalar::Str(b)) => a.partial_cmp(b),
+            (Scalar::Decimal(a), Scalar::Decimal(b)) => Some(a.compare(b)),
+            (Scalar::Decimal(a), Scalar::Int(b)) => Some(a.compare(&Decimal::new(*b as i128, 0))),
+            (Scalar::Int(a), Scalar::Decimal(b)) => Some(Decimal::new(*a as i128, 0).compare(b)),
+            (Scalar::Decimal(a), Scalar::Float(b)) => a.to_f64().partial_cmp(b),
+            (Scalar::Float(a), Scalar::Decimal(b)) => a.partial_cmp(&b.to_f64()),
+            _ => None,
+        }
+    }
+
+    /// Checks if the scalar can be compared with the values of a primitive column.
+    fn comparable_with(&self, column: &Type) -> bool {
+        let info = column.get_basic_info();
+        let decimal = matches!(info.logical_type(), Some(LogicalType::Decimal { .. }))
+            || info.converted_type() == ConvertedType::DECIMAL;
+        let string = matches!(
+            info.logical_type(),
+            Some(LogicalType::String | LogicalType::Enum | LogicalType::Json)
+        ) || matches!(
+            info.converted_type(),
+            ConvertedType::UTF8 | ConvertedType::ENUM | ConvertedType::JSON
+        );
+        let number = matches!(self, Scalar::Int(_) | Scalar::Float(_) | Scalar::Decimal(_));
+
+        match column.get_physical_type() {
+            _ if decimal => number,
+            PhysicalType::BOOLEAN => matches!(self, Scalar::Bool(_)),
+            PhysicalType::INT32
+            | PhysicalType::INT64
+            | PhysicalType::INT96
+            | PhysicalType::FLOAT
+            | PhysicalType::DOUBLE => number,
+            PhysicalType::BYTE_ARRAY if string => matches!(self, Scalar::Str(_)),
+            PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => false,
+        }
+    }
+
+    fn from_field(field: &Field) -> Option<Scalar> {
+        match field {
+            Field::Bool(v) => Some(Scalar::Bool(*v)),
+            Field::Byte(v) => Some(Scalar::Int(*v as i64)),
+            Field::Short(v) => Some(Scalar::Int(*v as i64)),
+            Field::Int(v) => Some(Scalar::Int(*v as i64)),
+            Field::Long(v) => Some(Scalar::Int(*v)),
+            Field::UByte(v) => Some(Scalar::Int(*v as i64)),
+            Field::UShort(v) => Some(Scalar::Int(*v as i64)),
+            Field::UInt(v) => Some(Scalar::Int(*v as i64)),
+            Field::ULong(v) => match i64::try_from(*v) {
+                Ok(v) => Some(Scalar::Int(v)),
+                Err(_) => Some(Scalar::Float(*v as f64)),
+            },
+            Field::Float(v) => Some(Scalar::Float(*v as f64)),
+            Field::Double(v) => Some(Scalar::Float(*v)),
+            Field::Str(v) => Some(Scalar::Str(v.clone())),
+            Field::Date(v) => Some(Scalar::Int(*v as i64)),
+            Field::TimestampMillis(v) => Some(Scalar::Int(*v)),
+            Field::TimestampMicros(v) => Some(Scalar::Int(*v)),
+            Field::Decimal(v) => Decimal::from_parquet(v).ok().map(Scalar::Decimal),
+            _ => None,
+        }
+    }
+
+    fn from_statistics(statistics: &Statistics) -> Option<(Scalar, Scalar)> {
+        if !statistics.has_min_max_set() {
+            return None;
+        }
+
+        match statistics {
+            Statistics::Boolean(s) => Some((Scalar::Bool(*s.min()), Scalar::Bool(*s.max()))),
+            Statistics::Int32(s) => {
+                Some((Scalar::Int(*s.min() as i64), Scalar::Int(*s.max() as i64)))
+            }
+            Statistics::Int64(s) => Some((Scalar::Int(*s.min()), Scalar::Int(*s.max()))),
+            Statistics::Float(s) => Some((
+                Scalar::Float(*s.min() as f64),
+                Scalar::Float(*s.max() as f64),
+            )),
+            Statistics::Double(s) => Some((Scalar::Float(*s.min()), Scalar::Float(*s.max()))),
+            Statistics::ByteArray(s) => match (s.min().as_utf8(), s.max().as_utf8()) {
+                (Ok(min), Ok(max)) => {
+                    Some((Scalar::Str(min.to_string()), Scalar::Str(max.to_string())))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Min and max of a DECIMAL column, its statistics are the unscaled values.
+    fn from_decimal_statistics(
+        statistics: &Statistics,
+        precision: i32,
+        scale: u32,
+    ) -> Option<(Scalar, Scalar)> {
+        if !statistics.has_min_max_set() {
+            return None;
+        }
+
+        let decimal = |unscaled: i128| Scalar::Decimal(Decimal::new(unscaled, scale));
+        let bytes = |data: &[u8]| Decimal::from_be_bytes(data, precision, scale).ok();
+        match statistics {
+            Statistics::Int32(s) => Some((decimal(*s.min() as i128), decimal(*s.max() as i128))),
+            Statistics::Int64(s) => Some((decimal(*s.min() as i128), decimal(*s.max() as i128))),
+            Statistics::ByteArray(s) => match (bytes(s.min().data()), bytes(s.max().data())) {
+                (Some(min), Some(max)) => Some((Scalar::Decimal(min), Scalar::Decimal(max))),
+                _ => None,
+            },
+            Statistics::FixedLenByteArray(s) => {
+                match (bytes(s.min().data()), bytes(s.max().data())) {
+                    (Some(min), Some(max)) => Some((Scalar::Decimal(min), Scalar::Decimal(max))),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_from_scalar {
+    ($variant:ident, $cast:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Scalar {
+                fn from(value: $t) -> Self {
+                    Scalar::$variant(value as $cast)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_scalar!(Int, i64, i8, i16, i32, i64, u8, u16, u32);
+impl_from_scalar!(Float, f64, f32, f64);
+
+impl From<bool> for Scalar {
+    fn from(value: bool) -> Self {
+        Scalar::Bool(value)
+    }
+}
+
+impl From<Decimal> for Scalar {
+    fn from(value: Decimal) -> Self {
+        Scalar::Decimal(value)
+    }
+}
+
+impl From<&str> for Scalar {
+    fn from(value: &str) -> Self {
+        Scalar::Str(value.to_string())
+    }
+}
+
+impl From<String> for Scalar {
+    fn from(value: String) -> Self {
+        Scalar::Str(value)
+    }
+}
+
+/// A condition over a top level column of a parquet file.
+/// Null values never match a predicate.
+/// Example:
+/// ```
+/// use combee::predicate::Predicate;
+///
+/// let predicates = [
+///     Predicate::ge("age", 18),
+///     Predicate::is_in("name", vec!["Daniel", "Sergio"]),
+/// ];
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Predicate {
+    /// The column is equal to the value.
+    Eq(String, Scalar),
+    /// The column is inside the range.
+    Range(String, Bound<Scalar>, Bound<Scalar>),
+    /// The column is equal to one of the values.
+    In(String, Vec<Scalar>),
+}
+
+impl Predicate {
+    /// column == value
+    pub fn eq<V: Into<Scalar>>(column: &str, value: V) -> Self {
+        Predicate::Eq(column.to_string(), value.into())
+    }
+
+    /// column > value
+    pub fn gt<V: Into<Scalar>>(column: &str, value: V) -> Self {
+        Predicate::Range(
+            column.to_string(),
+            Bound::Excluded(value.into()),
+            Bound::Unbounded,
+        )
+    }
+
+    /// column >= value
+    pub fn ge<V: Into<Scalar>>(column: &str, value: V) -> Self {
+        Predicate::Range(
+            column.to_string(),
+            Bound::Included(value.into()),
+            Bound::Unbounded,
+        )
+    }
+
+    /// column < value
+    pub fn lt<V: Into<Scalar>>(column: &str, value: V) -> Self {
+        Predicate::Range(
+            column.to_string(),
+            Bound::Unbounded,
+            Bound::Excluded(value.into()),
+        )
+    }
+
+    /// column <= value
+    pub fn le<V: Into<Scalar>>(column: &str, value: V) -> Self {
+        Predicate::Range(
+            column.to_string(),
+            Bound::Unbounded,
+            Bound::Included(value.into()),
+        )
+    }
+
+    /// min <= column <= max
+    pub fn between<V: Into<Scalar>>(column: &str, min: V, max: V) -> Self {
+        Predicate::Range(
+            column.to_string(),
+            Bound::Included(min.into()),
+            Bound::Included(max.into()),
+        )
+    }
+
+    /// column is one of the values
+    pub fn is_in<V: Into<Scalar>>(column: &str, values: Vec<V>) -> Self {
+        Predicate::In(
+            column.to_string(),
+            values.into_iter().map(|v| v.into()).collect(),
+        )
+    }
+
+    /// Name of the column used by the predicate.
+    pub fn column(&self) -> &str {
+        match self {
+            Predicate::Eq(column, _) => column,
+            Predicate::Range(column, _, _) => column,
+            Predicate::In(column, _) => column,
+        }
+    }
+
+    fn scalars(&self) -> Vec<&Scalar> {
+        match self {
+            Predicate::Eq(_, v) => vec![v],
+            Predicate::Range(_, min, max) => [min, max]
+                .into_iter()
+                .filter_map(|b| match b {
+                    Bound::Included(v) | Bound::Excluded(v) => Some(v),
+                    Bound::Unbounded => None,
+                })
+                .collect(),
+            Predicate::In(_, values) => values.iter().collect(),
+        }
+    }
+
+    /// Checks that the values of the predicate can be compared with the column,
+    /// otherwise every row would silently be filtered out.
+    pub(crate) fn check(&self, column: &Type) -> Result<(), errors::Error> {
+        if !column.is_primitive() {
+            return Err(errors::Error {
+                message: format!(
+                    "Column {} of the predicate is not a primitive column!",
+                    self.column()
+                ),
+            });
+        }
+        if column.get_basic_info().converted_type() == ConvertedType::DECIMAL
+            || matches!(
+                column.get_basic_info().logical_type(),
+                Some(LogicalType::Decimal { .. })
+            )
+        {
+            parquet_scale(column.get_scale())?;
+        }
+
+        match self.scalars().into_iter().find(|v| !v.comparable_with(column)) {
+            Some(v) => Err(errors::Error {
+                message: format!(
+                    "Value {:?} of the predicate can not be compared with column {}!",
+                    v,
+                    self.column()
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn matches_value(&self, value: &Scalar) -> bool {
+        match self {
+            Predicate::Eq(_, v) => value.compare(v) == Some(Ordering::Equal),
+            Predicate::Range(_, min, max) => {
+                let above = match min {
+                    Bound::Included(m) => {
+                        matches!(value.compare(m), Some(Ordering::Greater | Ordering::Equal))
+                    }
+                    Bound::Excluded(m) => value.compare(m) == Some(Ordering::Greater),
+                    Bound::Unbounded => true,
+                };
+                let below = match max {
+                    Bound::Included(m) => {
+                        matches!(value.compare(m), Some(Ordering::Less | Ordering::Equal))
+                    }
+                    Bound::Excluded(m) => value.compare(m) == Some(Ordering::Less),
+                    Bound::Unbounded => true,
+                };
+                above && below
+            }
+            Predicate::In(_, values) => values
+                .iter()
+                .any(|v| value.compare(v) == Some(Ordering::Equal)),
+        }
+    }
+
+    fn may_match_range(&self, min: &Scalar, max: &Scalar) -> bool {
+        // Values outside of [min, max] can not be in the row group.
+        let inside = |v: &Scalar| {
+            !matches!(v.compare(min), Some(Ordering::Less))
+                && !matches!(v.compare(max), Some(Ordering::Greater))
+        };
+
+        match self {
+            Predicate::Eq(_, v) => inside(v),
+            Predicate::Range(_, lower, upper) => {
+                let below_max = match lower {
+                    Bound::Included(l) => !matches!(l.compare(max), Some(Ordering::Greater)),
+                    Bound::Excluded(l) => {
+                        !matches!(l.compare(max), Some(Ordering::Greater | Ordering::Equal))
+                    }
+                    Bound::Unbounded => true,
+                };
+                let above_min = match upper {
+                    Bound::Included(u) => !matches!(u.compare(min), Some(Ordering::Less)),
+                    Bound::Excluded(u) => {
+                        !matches!(u.compare(min), Some(Ordering::Less | Ordering::Equal))
+                    }
+                    Bound::Unbounded => true,
+                };
+                below_max && above_min
+            }
+            Predicate::In(_, values) => values.iter().any(inside),
+        }
+    }
+
+    /// Checks if a row of a parquet matches the predicate.
+    pub(crate) fn matches(&self, row: &Row) -> bool {
+        match row
+            .get_column_iter()
+            .find(|(name, _)| *name == self.column())
+        {
+            Some((_, field)) => match Scalar::from_field(field) {
+                Some(value) => self.matches_value(&value),
+                None => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Checks, using the statistics, if some row of the row group may match the predicate.
+    pub(crate) fn may_match(&self, metadata: &RowGroupMetaData) -> bool {
+        let column = metadata.columns().iter().find(|c| {
+            let parts = c.column_path().parts();
+            parts.len() == 1 && parts[0] == self.column()
+        });
+
+        let column = match column {
+            Some(c) => c,
+            None => return true,
+        };
+
+        // Unsigned integers are stored as signed, their statistics can not be compared.
+        let descriptor = column.column_descr();
+        let unsigned = matches!(
+            descriptor.logical_type(),
+            Some(LogicalType::Integer {
+                is_signed: false,
+                ..
+            })
+        ) || matches!(
+            descriptor.converted_type(),
+            ConvertedType::UINT_8
+                | ConvertedType::UINT_16
+                | ConvertedType::UINT_32
+                | ConvertedType::UINT_64
+        );
+        if unsigned {
+            return true;
+        }
+
+        let decimal = matches!(descriptor.logical_type(), Some(LogicalType::Decimal { .. }))
+            || descriptor.converted_type() == ConvertedType::DECIMAL;
+        let statistics = match column.statistics() {
+            // Negative scales are rejected when the predicate is checked.
+            Some(s) if decimal => match parquet_scale(descriptor.type_scale()) {
+                Ok(scale) => {
+                    Scalar::from_decimal_statistics(s, descriptor.type_precision(), scale)
+                }
+                Err(_) => None,
+            },
+            Some(s) => Scalar::from_statistics(s),
+            None => None,
+        };
+
+        match statistics {
+            Some((min, max)) => self.may_match_range(&min, &max),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{
+    ser::{self, Impossible},
+    Serialize, Serializer,
+};
+
+use crate::errors;
+
+/// Prefix of the newtype names used by combee to mark values with a special parquet type (e.g. decimals).
+pub const MARKER_PREFIX: &str = "combee::";
+
+/// Calls `found` with the field name and the marker of the marked top level fields of the rows. Unmarked fields, None values and rows that are not structs are skipped.
+/// Rows are only probed until every field has had a value, so `found` is called at least once
+/// for each marked field with a value, but not for every row.
+pub fn marked_fields<T: Serialize, F>(rows: &[T], mut found: F)
+where
+    F: FnMut(&'static str, &'static str),
+{
+    let mut fields = 0;
+    let mut valued: Vec<&'static str> = Vec::new();
+    for row in rows {
+        let _ = row.serialize(RowProbe {
+            found: &mut found,
+            fields: &mut fields,
+            valued: &mut valued,
+        });
+        if valued.len() >= fields {
+            break;
+        }
+    }
+}
+
+/// Serializer that probes the fields of a struct.
+struct RowProbe<'a, F> {
+    found: &'a mut F,
+    /// Number of fields of the struct.
+    fields: &'a mut usize,
+    /// Fields that already had a value.
+    valued: &'a mut Vec<&'static str>,
+}
+
+/// Value of a field found by the probe.
+enum Probed {
+    Null,
+    Value,
+    Marked(&'static str),
+}
+
+/// Serializer that returns the marker of a marked value.
+struct FieldProbe;
+
+impl FieldProbe {
+    fn scalar(self) -> Result<Probed, errors::Error> {
+        Ok(Probed::Value)
+    }
+}
+
+fn not_a_scalar<T>() -> Result<T, errors::Error> {
+    Err(ser::Error::custom("Not a scalar!"))
+}
+
+macro_rules! probe_scalars {
+    ($($method:ident: $t:ty),*) => {
+        $(
+            fn $method(self, _: $t) -> Result<Self::Ok, errors::Error> {
+                self.scalar()
+            }
+        )*
+    };
+}
+
+macro_rules! probe_compounds {
+    () => {
+        type SerializeSeq = Impossible<Self::Ok, errors::Error>;
+        type SerializeTuple = Impossible<Self::Ok, errors::Error>;
+        type SerializeTupleStruct = Impossible<Self::Ok, errors::Error>;
+        type SerializeTupleVariant = Impossible<Self::Ok, errors::Error>;
+        type SerializeMap = Impossible<Self::Ok, errors::Error>;
+        type SerializeStructVariant = Impossible<Self::Ok, errors::Error>;
+
+        probe_scalars! {
+            serialize_bool: bool, serialize_i8: i8, serialize_i16: i16, serialize_i32: i32,
+            serialize_i64: i64, serialize_u8: u8, serialize_u16: u16, serialize_u32: u32,
+            serialize_u64: u64, serialize_f32: f32, serialize_f64: f64, serialize_char: char,
+            serialize_bytes: &[u8], serialize_unit_struct: &'static str
+        }
+
+        fn serialize_unit(self) -> Result<Self::Ok, errors::Error> {
+            self.scalar()
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+        ) -> Result<Self::Ok, errors::Error> {
+            self.scalar()
+        }
+
+        fn serialize_newtype_variant<V: Serialize + ?Sized>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &V,
+        ) -> Result<Self::Ok, errors::Error> {
+            self.scalar()
+        }
+
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, errors::Error> {
+            not_a_scalar()
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, errors::Error> {
+            not_a_scalar()
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleStruct, errors::Error> {
+            not_a_scalar()
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, errors::Error> {
+            not_a_scalar()
+        }
+
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, errors::Error> {
+            not_a_scalar()
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, errors::Error> {
+            not_a_scalar()
+        }
+    };
+}
+
+impl<'a, F> RowProbe<'a, F> {
+    fn scalar(self) -> Result<(), errors::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, F> Serializer for RowProbe<'a, F>
+where
+    F: FnMut(&'static str, &'static str),
+{
+    type Ok = ();
+    type Error = errors::Error;
+    type SerializeStruct = Self;
+
+    probe_compounds!();
+
+    fn serialize_str(self, _: &str) -> Result<(), errors::Error> {
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), errors::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<V: Serialize + ?Sized>(self, value: &V) -> Result<(), errors::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<V: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &V,
+    ) -> Result<(), errors::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<Self, errors::Error> {
+        // Fields skipped by serde in some rows are not counted in their length.
+        *self.fields = (*self.fields).max(len);
+        Ok(self)
+    }
+}
+
+impl<'a, F> ser::SerializeStruct for RowProbe<'a, F>
+where
+    F: FnMut(&'static str, &'static str),
+{
+    type Ok = ();
+    type Error = errors::Error;
+
+    fn serialize_field<V: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &V,
+    ) -> Result<(), errors::Error> {
+        let probed = match value.serialize(FieldProbe) {
+            Ok(Probed::Null) => return Ok(()),
+            Ok(probed) => probed,
+            // Values that are not scalars (e.g. nested structs) are not marked.
+            Err(_) => Probed::Value,
+        };
+
+        if let Probed::Marked(marker) = probed {
+            (self.found)(key, marker);
+        }
+        if !self.valued.contains(&key) {
+            self.valued.push(key);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), errors::Error> {
+        Ok(())
+    }
+}
+
+impl Serializer for FieldProbe {
+    type Ok = Probed;
+    type Error = errors::Error;
+    type SerializeStruct = Impossible<Self::Ok, errors::Error>;
+
+    probe_compounds!();
+
+    fn serialize_str(self, _: &str) -> Result<Self::Ok, errors::Error> {
+        self.scalar()
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, errors::Error> {
+        Ok(Probed::Null)
+    }
+
+    fn serialize_some<V: Serialize + ?Sized>(self, value: &V) -> Result<Self::Ok, errors::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<V: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &V,
+    ) -> Result<Self::Ok, errors::Error> {
+        if !name.starts_with(MARKER_PREFIX) {
+            return value.serialize(self);
+        }
+        Ok(Probed::Marked(name))
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, errors::Error> {
+        not_a_scalar()
+    }
+}
@@ -0,0 +1,99 @@
+use parquet::schema::types::{Type, TypePtr};
+use serde::{
+    de::{self, Deserialize, Visitor},
+    forward_to_deserialize_any,
+};
+
+use crate::errors::Error;
+
+/// Deserializer that only records the fields requested by the struct being deserialized.
+struct FieldsTracer {
+    fields: Option<&'static [&'static str]>,
+}
+
+impl<'de> de::Deserializer<'de> for &mut FieldsTracer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error {
+            message: "Not a struct!".to_string(),
+        })
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.fields = Some(fields);
+        Err(Error {
+            message: "Fields traced!".to_string(),
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit seq
+            bytes byte_buf map tuple_struct identifier
+            tuple ignored_any unit_struct enum option newtype_struct
+    }
+}
+
+/// Returns the names of the top level fields of the struct D.
+/// Returns None if D is not a plain struct (e.g. maps, tuples or structs with flattened fields).
+pub fn struct_fields<'de, D: Deserialize<'de>>() -> Option<&'static [&'static str]> {
+    let mut tracer = FieldsTracer { fields: None };
+    let _ = D::deserialize(&mut tracer);
+    tracer.fields
+}
+
+/// Builds a projection of the parquet schema with only the given top level columns.
+pub fn project(root: &Type, columns: &[String]) -> Result<Type, Error> {
+    let mut fields: Vec<TypePtr> = Vec::new();
+
+    for column in columns {
+        match root.get_fields().iter().find(|f| f.name() == column) {
+            Some(field) => fields.push(field.clone()),
+            None => {
+                return Err(Error {
+                    message: format!("Column {} not found in parquet!", column),
+                })
+            }
+        }
+    }
+
+    match Type::group_type_builder(root.name())
+        .with_fields(fields)
+        .build()
+    {
+        Ok(t) => Ok(t),
+        Err(e) => Err(Error {
+            message: e.to_string(),
+        }),
+    }
+}
+
+/// Builds a projection of the parquet schema with the columns used by the struct D and the extra columns.
+/// Returns None when every column must be read.
+pub fn project_struct<'de, D: Deserialize<'de>>(root: &Type, extra: &[String]) -> Option<Type> {
+    let fields = struct_fields::<D>()?;
+
+    let columns: Vec<String> = root
+        .get_fields()
+        .iter()
+        .filter(|f| fields.contains(&f.name()) || extra.iter().any(|c| c == f.name()))
+        .map(|f| f.name().to_string())
+        .collect();
+
+    if columns.is_empty() || columns.len() == root.get_fields().len() {
+        return None;
+    }
+
+    project(root, &columns).ok()
+}
@@ -0,0 +1,554 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    f64::consts::PI,
+    hash::{Hash, Hasher},
+    sync::OnceLock,
+};
+
+use crate::{dataframe::Group, errors};
+
+/// Buffered values of a t-digest compressed together into centroids.
+const TDIGEST_BUFFER: usize = 1024;
+
+/// Sketch of the distinct values inserted, for approximate distinct counts in constant memory.
+/// It uses `2^precision` one byte registers and its relative error is about `1.04 / sqrt(2^precision)`,
+/// e.g. 0.8% with the default precision of 14. Values are hashed with a fixed hash that writes integers
+/// in little endian and `usize`/`isize` as 64 bits, so sketches of the same Rust types serialized by separate
+/// runs, or on other platforms, can be merged as long as they have the same precision.
+/// Example:
+/// ```
+/// use combee::sketches::HyperLogLog;
+///
+/// let mut first = HyperLogLog::default();
+/// let mut second = HyperLogLog::default();
+/// for i in 0..1000 {
+///     first.insert(&i);
+///     second.insert(&(i + 500));
+/// }
+/// first.merge(&second).unwrap();
+///
+/// let estimate = first.estimate() as f64;
+/// assert!((estimate - 1500.0).abs() < 1500.0 * 0.05);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawHyperLogLog")]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+/// Deserialized HyperLogLog, validated before use.
+#[derive(Deserialize)]
+struct RawHyperLogLog {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl TryFrom<RawHyperLogLog> for HyperLogLog {
+    type Error = errors::Error;
+
+    fn try_from(raw: RawHyperLogLog) -> Result<Self, errors::Error> {
+        let mut sketch = HyperLogLog::new(raw.precision)?;
+        let max_rank = 64 - raw.precision + 1;
+        if raw.registers.len() != sketch.registers.len()
+            || raw.registers.iter().any(|r| *r > max_rank)
+        {
+            return Err(errors::Error {
+                message: format!(
+                    "Invalid registers for a HyperLogLog with precision {}!",
+                    raw.precision
+                ),
+            });
+        }
+
+        sketch.registers = raw.registers;
+        Ok(sketch)
+    }
+}
+
+impl HyperLogLog {
+    /// Instantiate an empty sketch, the precision must be between 4 and 16.
+    pub fn new(precision: u8) -> Result<Self, errors::Error> {
+        if !(4..=16).contains(&precision) {
+            return Err(errors::Error {
+                message: format!(
+                    "HyperLogLog precision must be between 4 and 16, got {}!",
+                    precision
+                ),
+            });
+        }
+
+        Ok(HyperLogLog {
+            precision,
+            registers: vec![0; 1 << precision],
+        })
+    }
+
+    /// Precision of the sketch.
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Adds a value to the sketch.
+    pub fn insert<N: Hash + ?Sized>(&mut self, value: &N) {
+        let mut hasher = StableHasher::default();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let precision = self.precision as u32;
+        let index = (hash >> (64 - precision)) as usize;
+        let rank = ((hash << precision).leading_zeros() + 1).min(64 - precision + 1) as u8;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    /// Adds the values of another sketch, both sketches must have the same precision.
+    pub fn merge(&mut self, other: &HyperLogLog) -> Result<(), errors::Error> {
+        if self.precision != other.precision {
+            return Err(errors::Error {
+                message: format!(
+                    "Can not merge HyperLogLog sketches with precisions {} and {}!",
+                    self.precision, other.precision
+                ),
+            });
+        }
+
+        for (register, other) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *register = (*register).max(*other);
+        }
+        Ok(())
+    }
+
+    /// Approximate number of distinct values inserted.
+    pub fn estimate(&self) -> usize {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+
+        let sum: f64 = self.registers.iter().map(|r| 2f64.powi(-(*r as i32))).sum();
+        let estimate = alpha * m * m / sum;
+
+        // Linear counting is more accurate for small cardinalities.
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            (m * (m / zeros as f64).ln()).round() as usize
+        } else {
+            estimate.round() as usize
+        }
+    }
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        HyperLogLog {
+            precision: 14,
+            registers: vec![0; 1 << 14],
+        }
+    }
+}
+
+/// Centroid of a t-digest: the mean of a cluster of values and its number of values.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Centroid {
+    pub mean: f64,
+    pub weight: f64,
+}
+
+/// Sketch of the values inserted, for approximate quantiles in bounded memory.
+/// Values are clustered into at most about `compression` centroids, smaller near the tails,
+/// so extreme quantiles are more accurate than the median. Sketches can be serialized and merged.
+/// NaN and infinite values are ignored.
+/// Example:
+/// ```
+/// use combee::sketches::TDigest;
+///
+/// let mut first = TDigest::default();
+/// let mut second = TDigest::default();
+/// for i in 0..1000 {
+///     first.insert(i as f64);
+///     second.insert((i + 1000) as f64);
+/// }
+/// first.merge(&second).unwrap();
+///
+/// let median = first.quantile(0.5).unwrap();
+/// assert!((median - 1000.0).abs() < 20.0);
+/// assert_eq!(first.quantile(1.0), Some(1999.0));
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "RawTDigest")]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    count: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+    /// Compressed centroids, computed by the first query after an insert or a merge.
+    #[serde(skip)]
+    compressed: OnceLock<Vec<Centroid>>,
+}
+
+impl PartialEq for TDigest {
+    fn eq(&self, other: &Self) -> bool {
+        self.compression == other.compression
+            && self.centroids == other.centroids
+            && self.buffer == other.buffer
+            && self.count == other.count
+            && self.min == other.min
+            && self.max == other.max
+    }
+}
+
+/// Deserialized TDigest, validated before use.
+#[derive(Deserialize)]
+struct RawTDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    count: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl TryFrom<RawTDigest> for TDigest {
+    type Error = errors::Error;
+
+    fn try_from(raw: RawTDigest) -> Result<Self, errors::Error> {
+        let sketch = TDigest::new(raw.compression)?;
+        let weight: f64 = raw.centroids.iter().map(|c| c.weight).sum();
+        let valid = raw
+            .centroids
+            .iter()
+            .all(|c| c.mean.is_finite() && c.weight.is_finite() && c.weight > 0.0)
+            && raw.buffer.iter().all(|v| v.is_finite())
+            && raw.count == weight + raw.buffer.len() as f64
+            && (raw.count == 0.0) == (raw.min.is_none() || raw.max.is_none())
+            && raw.min.is_none_or(f64::is_finite)
+            && raw.max.is_none_or(f64::is_finite);
+        if !valid {
+            return Err(errors::Error {
+                message: "Invalid centroids or count for a TDigest!".to_string(),
+            });
+        }
+
+        Ok(TDigest {
+            centroids: raw.centroids,
+            buffer: raw.buffer,
+            count: raw.count,
+            min: raw.min,
+            max: raw.max,
+            ..sketch
+        })
+    }
+}
+
+impl TDigest {
+    /// Instantiate an empty sketch, the compression must be at least 10.
+    pub fn new(compression: f64) -> Result<Self, errors::Error> {
+        if compression.is_nan() || compression < 10.0 {
+            return Err(errors::Error {
+                message: format!(
+                    "TDigest compression must be at least 10, got {}!",
+                    compression
+                ),
+            });
+        }
+
+        Ok(TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0.0,
+            min: None,
+            max: None,
+            compressed: OnceLock::new(),
+        })
+    }
+
+    /// Compression of the sketch.
+    pub fn compression(&self) -> f64 {
+        self.compression
+    }
+
+    /// Number of values inserted.
+    pub fn count(&self) -> usize {
+        self.count as usize
+    }
+
+    /// Adds a value to the sketch, NaN and infinite values are ignored.
+    pub fn insert(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+
+        self.compressed.take();
+        self.buffer.push(value);
+        self.count += 1.0;
+        self.min = Some(self.min.map_or(value, |m| m.min(value)));
+        self.max = Some(self.max.map_or(value, |m| m.max(value)));
+        if self.buffer.len() >= TDIGEST_BUFFER {
+            self.compress();
+        }
+    }
+
+    /// Adds the values of another sketch, both sketches must have the same compression.
+    pub fn merge(&mut self, other: &TDigest) -> Result<(), errors::Error> {
+        if self.compression != other.compression {
+            return Err(errors::Error {
+                message: format!(
+                    "Can not merge TDigest sketches with compressions {} and {}!",
+                    self.compression, other.compression
+                ),
+            });
+        }
+
+        self.compressed.take();
+        self.centroids.extend(other.centroids.iter().copied());
+        self.buffer.extend(other.buffer.iter().copied());
+        self.count += other.count;
+        self.min = match (self.min, other.min) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.max = match (self.max, other.max) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        self.compress();
+        Ok(())
+    }
+
+    /// Centroids of the sketch, ordered by mean.
+    pub fn centroids(&self) -> Vec<Centroid> {
+        self.compressed().to_vec()
+    }
+
+    /// Approximate value at the quantile `q` of the values inserted, q must be between 0 and 1.
+    /// Returns None for an empty sketch or an invalid quantile.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        let (min, max) = match (self.min, self.max) {
+            (Some(min), Some(max)) if (0.0..=1.0).contains(&q) => (min, max),
+            _ => return None,
+        };
+
+        let centroids = self.compressed();
+        if centroids.len() == 1 {
+            return Some(centroids[0].mean);
+        }
+
+        // Values are interpolated between the centers of the centroids, and the extremes at both ends.
+        let target = q * self.count;
+        let mut previous = (0.0, min);
+        let mut cumulative = 0.0;
+        for centroid in centroids.iter() {
+            let center = (cumulative + centroid.weight / 2.0, centroid.mean);
+            if target <= center.0 {
+                return Some(interpolate(previous, center, target));
+            }
+            cumulative += centroid.weight;
+            previous = center;
+        }
+        Some(interpolate(previous, (self.count, max), target))
+    }
+
+    /// Centroids of the sketch with the buffer compressed, computed once until the next insert or merge.
+    fn compressed(&self) -> &[Centroid] {
+        self.compressed.get_or_init(|| {
+            let mut digest = self.clone();
+            digest.compress();
+            digest.centroids
+        })
+    }
+
+    /// Merges the buffer and the centroids, keeping clusters within the size limit of the scale function.
+    fn compress(&mut self) {
+        if self.buffer.is_empty() && self.centroids.len() <= 1 {
+            return;
+        }
+
+        let mut centroids = std::mem::take(&mut self.centroids);
+        centroids.extend(self.buffer.drain(..).map(|value| Centroid {
+            mean: value,
+            weight: 1.0,
+        }));
+        centroids.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let total: f64 = centroids.iter().map(|c| c.weight).sum();
+        let mut merged = Vec::new();
+        let mut current = centroids[0];
+        let mut weight_before = 0.0;
+        let mut limit = self.q_limit(0.0);
+        for centroid in centroids.into_iter().skip(1) {
+            if (weight_before + current.weight + centroid.weight) / total <= limit {
+                let weight = current.weight + centroid.weight;
+                current.mean += (centroid.mean - current.mean) * centroid.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_before += current.weight;
+                merged.push(current);
+                limit = self.q_limit(weight_before / total);
+                current = centroid;
+            }
+        }
+        merged.push(current);
+
+        self.centroids = merged;
+    }
+
+    /// Largest quantile a centroid starting at quantile `q` can reach: one unit of the scale function
+    /// `k(q) = compression / (2 * PI) * asin(2 * q - 1)`.
+    fn q_limit(&self, q: f64) -> f64 {
+        let k = self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin() + 1.0;
+        if k >= self.compression / 4.0 {
+            1.0
+        } else {
+            ((2.0 * PI * k / self.compression).sin() + 1.0) / 2.0
+        }
+    }
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        TDigest {
+            compression: 100.0,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0.0,
+            min: None,
+            max: None,
+            compressed: OnceLock::new(),
+        }
+    }
+}
+
+/// HyperLogLog sketch of the values of the property in the group, see [HyperLogLog].
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::all, sketches::hyperloglog};
+///
+/// let df = DataFrame::new((0..10000).map(|i| i % 2500).collect());
+/// let result = df.groupby(all).agg(|_, g| hyperloglog(g, 14, |x| *x).unwrap().estimate());
+///
+/// let estimate = result.take(1)[0] as f64;
+/// assert!((estimate - 2500.0).abs() < 2500.0 * 0.05);
+/// ```
+pub fn hyperloglog<D: Clone + Serialize + DeserializeOwned, N: Hash, F>(
+    group: &Group<D>,
+    precision: u8,
+    property: F,
+) -> Result<HyperLogLog, errors::Error>
+where
+    F: Fn(&D) -> N,
+{
+    let mut sketch = HyperLogLog::new(precision)?;
+    for row in group.iter() {
+        sketch.insert(&property(row));
+    }
+    Ok(sketch)
+}
+
+/// T-digest sketch of the values of the property in the group, see [TDigest].
+/// Example:
+/// ```
+/// use combee::{dataframe::DataFrame, functions::all, sketches::tdigest};
+///
+/// let df = DataFrame::new((1..=1000).collect());
+/// let result = df.groupby(all).agg(|_, g| tdigest(g, 100.0, |x| *x as f64).unwrap().quantile(0.9));
+///
+/// let p90 = result.take(1)[0].unwrap();
+/// assert!((p90 - 900.0).abs() < 10.0);
+/// ```
+pub fn tdigest<D: Clone + Serialize + DeserializeOwned, F>(
+    group: &Group<D>,
+    compression: f64,
+    property: F,
+) -> Result<TDigest, errors::Error>
+where
+    F: Fn(&D) -> f64,
+{
+    let mut sketch = TDigest::new(compression)?;
+    for row in group.iter() {
+        sketch.insert(property(row));
+    }
+    Ok(sketch)
+}
+
+fn interpolate(from: (f64, f64), to: (f64, f64), x: f64) -> f64 {
+    if to.0 <= from.0 {
+        return to.1;
+    }
+    from.1 + (to.1 - from.1) * (x - from.0) / (to.0 - from.0)
+}
+
+/// FNV-1a hash finished with the splitmix64 mixer. Unlike the standard library hasher its output is fixed,
+/// and integers are written in a canonical encoding (little endian, 64 bits for `usize` and `isize`),
+/// so separate runs and platforms hash the values of the same Rust types the same way.
+#[derive(Clone, Copy, Debug)]
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut z = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,104 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::io::Read;
+
+use crate::{dataframe::DataFrame, errors};
+
+/// Iterator over the typed rows of a CSV, reading one row at a time.
+/// Created by [function@crate::scan_csv].
+pub struct CsvRowIter<D: Clone + DeserializeOwned + Serialize, R: Read> {
+    pub(crate) iter: csv::DeserializeRecordsIntoIter<R, D>,
+}
+
+/// Iterator over batches of rows of a CSV, each batch is a DataFrame.
+/// Created by [CsvRowIter::chunks].
+pub struct CsvChunkIter<D: Clone + DeserializeOwned + Serialize, R: Read> {
+    rows: CsvRowIter<D, R>,
+    size: usize,
+    error: Option<errors::Error>,
+}
+
+impl<D: Clone + DeserializeOwned + Serialize, R: Read> CsvRowIter<D, R> {
+    pub(crate) fn new(reader: csv::Reader<R>) -> Self {
+        CsvRowIter {
+            iter: reader.into_deserialize(),
+        }
+    }
+
+    /// Group the rows in DataFrames of at most 'size' rows, returns an error if 'size' is zero.
+    /// When a row fails to deserialize the rows read before it are returned as a shorter chunk,
+    /// then the error, and the next chunk starts after the invalid row.
+    /// Example:
+    /// ```
+    /// use combee::scan_csv;
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Clone, Serialize, Deserialize)]
+    /// struct D {
+    ///     name: String,
+    ///     age: u32
+    /// }
+    ///
+    /// for chunk in scan_csv::<D>("dataset.csv".to_string()).unwrap().chunks(2).unwrap() {
+    ///     let df = chunk.unwrap();
+    ///     println!("{}", df.filter(|row| row.age > 25).len());
+    /// }
+    /// ```
+    pub fn chunks(self, size: usize) -> Result<CsvChunkIter<D, R>, errors::Error> {
+        if size == 0 {
+            return Err(errors::Error {
+                message: "Chunk size must be greater than zero!".to_string(),
+            });
+        }
+
+        Ok(CsvChunkIter {
+            rows: self,
+            size,
+            error: None,
+        })
+    }
+}
+
+impl<D: Clone + DeserializeOwned + Serialize, R: Read> Iterator for CsvRowIter<D, R> {
+    type Item = Result<D, errors::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.iter.next() {
+            Some(Ok(row)) => Some(Ok(row)),
+            Some(Err(e)) => Some(Err(errors::Error {
+                message: e.to_string(),
+            })),
+            None => None,
+        }
+    }
+}
+
+impl<D: Clone + DeserializeOwned + Serialize, R: Read> Iterator for CsvChunkIter<D, R> {
+    type Item = Result<DataFrame<D>, errors::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.error.take() {
+            return Some(Err(e));
+        }
+
+        let mut data = Vec::with_capacity(self.size);
+
+        while data.len() < self.size {
+            match self.rows.next() {
+                Some(Ok(row)) => data.push(row),
+                Some(Err(e)) if data.is_empty() => return Some(Err(e)),
+                Some(Err(e)) => {
+                    // The error is kept for the next call, so the rows read before it are not lost.
+                    self.error = Some(e);
+                    break;
+                }
+                None => break,
+            }
+        }
+
+        if data.is_empty() {
+            None
+        } else {
+            Some(Ok(DataFrame::new(data)))
+        }
+    }
+}
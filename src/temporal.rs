@@ -0,0 +1,314 @@
+use arrow2::{
+    array::{Array, PrimitiveArray, StructArray},
+    datatypes::{DataType, TimeUnit},
+};
+use serde::{
+    de::{self, value::SeqAccessDeserializer, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{fmt, marker::PhantomData};
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::errors;
+
+const NANOS_PER_DAY: i128 = 86_400_000_000_000;
+
+const DATE_NAME: &str = "combee::Date";
+const PRIMITIVE_DATE_TIME_NAME: &str = "combee::PrimitiveDateTime";
+const OFFSET_DATE_TIME_NAME: &str = "combee::OffsetDateTime";
+
+/// Types of the `time` crate written as parquet DATE and TIMESTAMP columns.
+/// With the `serde` feature they are serialized as tuples of their components:
+/// - Date: (year, ordinal)
+/// - PrimitiveDateTime: (year, ordinal, hour, minute, second, nanosecond)
+/// - OffsetDateTime: (year, ordinal, hour, minute, second, nanosecond, offset hours, offset minutes, offset seconds)
+///
+/// The fields are marked with the serde helpers of this module, a plain tuple is never converted.
+/// Timestamps are written in microseconds, rounded down so sub-microsecond precision is truncated
+/// towards the past, and OffsetDateTime values are normalized to UTC: they are read back with a zero offset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Temporal {
+    Date,
+    PrimitiveDateTime,
+    OffsetDateTime,
+}
+
+impl Temporal {
+    /// Finds the `time` type of a field marked by one of the serde helpers.
+    pub(crate) fn from_marker(marker: &str) -> Option<Temporal> {
+        match marker {
+            DATE_NAME => Some(Temporal::Date),
+            PRIMITIVE_DATE_TIME_NAME => Some(Temporal::PrimitiveDateTime),
+            OFFSET_DATE_TIME_NAME => Some(Temporal::OffsetDateTime),
+            _ => None,
+        }
+    }
+
+    /// Number of components of the serialized `time` type.
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Temporal::Date => 2,
+            Temporal::PrimitiveDateTime => 6,
+            Temporal::OffsetDateTime => 9,
+        }
+    }
+
+    /// Arrow type written to parquet: DATE or TIMESTAMP(MICROS), adjusted to UTC for OffsetDateTime.
+    pub(crate) fn data_type(&self) -> DataType {
+        match self {
+            Temporal::Date => DataType::Date32,
+            Temporal::PrimitiveDateTime => DataType::Timestamp(TimeUnit::Microsecond, None),
+            Temporal::OffsetDateTime => {
+                DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".to_string()))
+            }
+        }
+    }
+}
+
+/// Value serialized as a newtype with the name of its marker.
+struct Marked<'a, T>(&'static str, &'a T);
+
+impl<'a, T: Serialize> Serialize for Marked<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(self.0, self.1)
+    }
+}
+
+/// Visitor of a marked value, formats without newtypes visit the components directly.
+struct MarkedVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for MarkedVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a date or datetime")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        T::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<T, A::Error> {
+        T::deserialize(SeqAccessDeserializer::new(seq))
+    }
+}
+
+/// Visitor of an optional marked value.
+struct OptionVisitor<T>(&'static str, PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for OptionVisitor<T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an optional date or datetime")
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Option<T>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<T>, D::Error> {
+        deserializer
+            .deserialize_newtype_struct(self.0, MarkedVisitor(PhantomData))
+            .map(Some)
+    }
+}
+
+macro_rules! temporal_serde {
+    ($module:ident, $option:ident, $t:ty, $marker:expr, $name:literal) => {
+        #[doc = concat!("Serde helpers for `time::", $name, "` fields read and written as parquet ")]
+        #[doc = concat!($name, " columns, use with `#[serde(with = \"combee::temporal::", stringify!($module), "\")]`.")]
+        pub mod $module {
+            use super::*;
+
+            pub fn serialize<S: Serializer>(value: &$t, serializer: S) -> Result<S::Ok, S::Error> {
+                Marked($marker, value).serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<$t, D::Error> {
+                deserializer.deserialize_newtype_struct($marker, MarkedVisitor(PhantomData))
+            }
+        }
+
+        #[doc = concat!("Serde helpers for `Option<time::", $name, ">` fields, see [crate::temporal::", stringify!($module), "].")]
+        pub mod $option {
+            use super::*;
+
+            pub fn serialize<S: Serializer>(
+                value: &Option<$t>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                match value {
+                    Some(v) => serializer.serialize_some(&Marked($marker, v)),
+                    None => serializer.serialize_none(),
+                }
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Option<$t>, D::Error> {
+                deserializer.deserialize_option(OptionVisitor($marker, PhantomData))
+            }
+        }
+    };
+}
+
+temporal_serde!(date, option_date, Date, DATE_NAME, "Date");
+temporal_serde!(
+    primitive_date_time,
+    option_primitive_date_time,
+    PrimitiveDateTime,
+    PRIMITIVE_DATE_TIME_NAME,
+    "PrimitiveDateTime"
+);
+temporal_serde!(
+    offset_date_time,
+    option_offset_date_time,
+    OffsetDateTime,
+    OFFSET_DATE_TIME_NAME,
+    "OffsetDateTime"
+);
+
+/// Components of the `time` type with `len` fields at `nanos` nanoseconds since epoch (UTC).
+/// Returns None when `len` does not match a `time` type.
+pub(crate) fn components(nanos: i128, len: usize) -> Option<Result<Vec<i64>, errors::Error>> {
+    if len != 2 && len != 6 && len != 9 {
+        return None;
+    }
+
+    let datetime = match OffsetDateTime::from_unix_timestamp_nanos(nanos) {
+        Ok(d) => d,
+        Err(e) => {
+            return Some(Err(errors::Error {
+                message: e.to_string(),
+            }))
+        }
+    };
+
+    let mut values = vec![datetime.year() as i64, datetime.ordinal() as i64];
+    if len >= 6 {
+        values.extend([
+            datetime.hour() as i64,
+            datetime.minute() as i64,
+            datetime.second() as i64,
+            datetime.nanosecond() as i64,
+        ]);
+    }
+    if len == 9 {
+        values.extend([0, 0, 0]);
+    }
+    Some(Ok(values))
+}
+
+/// Nanoseconds since epoch (UTC) of a number of days since epoch.
+pub(crate) fn days_to_nanos(days: i32) -> i128 {
+    days as i128 * NANOS_PER_DAY
+}
+
+/// Converts the struct array of serialized `time` values into a DATE or TIMESTAMP array.
+pub(crate) fn temporal_array(
+    array: &dyn Array,
+    temporal: Temporal,
+) -> Result<Box<dyn Array>, errors::Error> {
+    let invalid = || errors::Error {
+        message: format!("Can not write {:?} as {:?}!", array.data_type(), temporal),
+    };
+
+    let array = array
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .ok_or_else(invalid)?;
+    let children = array.values();
+
+    let mut values = Vec::with_capacity(array.len());
+    for row in 0..array.len() {
+        if !array.is_valid(row) {
+            values.push(None);
+            continue;
+        }
+
+        let year = child::<i32>(children, 0, row).ok_or_else(invalid)?;
+        let ordinal = child::<u16>(children, 1, row).ok_or_else(invalid)?;
+        let date = match Date::from_ordinal_date(year, ordinal) {
+            Ok(d) => d,
+            Err(e) => {
+                return Err(errors::Error {
+                    message: e.to_string(),
+                })
+            }
+        };
+
+        if temporal == Temporal::Date {
+            values.push(Some(
+                (date - OffsetDateTime::UNIX_EPOCH.date()).whole_days(),
+            ));
+            continue;
+        }
+
+        let time = Time::from_hms_nano(
+            child::<u8>(children, 2, row).ok_or_else(invalid)?,
+            child::<u8>(children, 3, row).ok_or_else(invalid)?,
+            child::<u8>(children, 4, row).ok_or_else(invalid)?,
+            child::<u32>(children, 5, row).ok_or_else(invalid)?,
+        );
+        let datetime = match time {
+            Ok(t) => PrimitiveDateTime::new(date, t),
+            Err(e) => {
+                return Err(errors::Error {
+                    message: e.to_string(),
+                })
+            }
+        };
+
+        let offset = if temporal == Temporal::OffsetDateTime {
+            UtcOffset::from_hms(
+                child::<i8>(children, 6, row).ok_or_else(invalid)?,
+                child::<i8>(children, 7, row).ok_or_else(invalid)?,
+                child::<i8>(children, 8, row).ok_or_else(invalid)?,
+            )
+        } else {
+            Ok(UtcOffset::UTC)
+        };
+        let datetime = match offset {
+            Ok(o) => datetime.assume_offset(o),
+            Err(e) => {
+                return Err(errors::Error {
+                    message: e.to_string(),
+                })
+            }
+        };
+
+        // Rounded down, so values before epoch are not moved forward.
+        values.push(Some(datetime.unix_timestamp_nanos().div_euclid(1_000) as i64));
+    }
+
+    match temporal {
+        Temporal::Date => Ok(PrimitiveArray::<i32>::from(
+            values
+                .into_iter()
+                .map(|v| v.map(|d| d as i32))
+                .collect::<Vec<_>>(),
+        )
+        .to(temporal.data_type())
+        .boxed()),
+        _ => Ok(PrimitiveArray::<i64>::from(values)
+            .to(temporal.data_type())
+            .boxed()),
+    }
+}
+
+fn child<T: arrow2::types::NativeType>(
+    children: &[Box<dyn Array>],
+    index: usize,
+    row: usize,
+) -> Option<T> {
+    children
+        .get(index)?
+        .as_any()
+        .downcast_ref::<PrimitiveArray<T>>()?
+        .get(row)
+}
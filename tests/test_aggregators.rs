@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{hash_map::Entry, HashMap};
+
+use combee::{
+    aggregators::{Aggregator, Count, Max, Mean, Min, Sum},
+    dataframe::DataFrame,
+    functions::{avg, count, max, min, sum},
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Person {
+    city: String,
+    age: u32,
+    height: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Stats {
+    city: String,
+    count: usize,
+    total: u32,
+    mean: f64,
+    youngest: Option<u32>,
+    oldest: Option<u32>,
+}
+
+fn people() -> DataFrame<Person> {
+    DataFrame::new(
+        (0..1000u32)
+            .map(|i| Person {
+                city: ["rio", "sp", "bh"][(i % 3) as usize].to_string(),
+                age: (i * 7) % 90,
+                height: 1.5 + (i % 50) as f64 / 100.0,
+            })
+            .collect(),
+    )
+}
+
+fn stats_aggregator(
+    city: String,
+) -> impl Aggregator<Person, State = impl Clone + Send, Output = Stats> {
+    (
+        Count,
+        Sum::of(|p: &Person| p.age),
+        Mean::of(|p: &Person| p.height),
+        Min::of(|p: &Person| p.age),
+        Max::of(|p: &Person| p.age),
+    )
+        .map(move |(count, total, mean, youngest, oldest)| Stats {
+            city: city.clone(),
+            count,
+            total,
+            mean,
+            youngest,
+            oldest,
+        })
+}
+
+#[test]
+fn test_aggregators_match_functions() {
+    let df = people();
+    let grouped = df.groupby(|p| p.city.clone());
+
+    let single_pass = grouped.agg(|city, g| stats_aggregator(city.clone()).aggregate(g));
+    let functions = grouped.agg(|city, g| Stats {
+        city: city.clone(),
+        count: count(g),
+        total: sum(g, |p| p.age),
+        mean: avg(g, |p| p.height),
+        youngest: Some(min(g, |p| p.age)),
+        oldest: Some(max(g, |p| p.age)),
+    });
+
+    assert_eq!(single_pass.take(3), functions.take(3));
+}
+
+#[test]
+fn test_aggregators_merge_chunks() {
+    let df = people();
+    let expected = df
+        .groupby(|p| p.city.clone())
+        .agg_sorted(|city, g| stats_aggregator(city.clone()).aggregate(g));
+
+    // Partial states of each chunk, computed in parallel and merged by key.
+    let rows = df.take(df.len());
+    let partials: Vec<HashMap<String, _>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = rows
+            .chunks(128)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut states = HashMap::new();
+                    for p in chunk {
+                        let aggregator = stats_aggregator(p.city.clone());
+                        let state = states
+                            .entry(p.city.clone())
+                            .or_insert_with(|| aggregator.init());
+                        aggregator.update(state, p);
+                    }
+                    states
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut merged = HashMap::new();
+    for states in partials {
+        for (city, state) in states {
+            match merged.entry(city) {
+                Entry::Occupied(mut e) => {
+                    let aggregator = stats_aggregator(e.key().clone());
+                    aggregator.merge(e.get_mut(), state);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(state);
+                }
+            }
+        }
+    }
+
+    let mut results: Vec<Stats> = merged
+        .into_iter()
+        .map(|(city, state)| stats_aggregator(city).finish(state))
+        .collect();
+    results.sort_by(|a, b| a.city.cmp(&b.city));
+
+    let expected = expected.take(3);
+    for (result, expected) in results.iter().zip(expected.iter()) {
+        assert_eq!(result.count, expected.count);
+        assert_eq!(result.total, expected.total);
+        assert_eq!(result.youngest, expected.youngest);
+        assert_eq!(result.oldest, expected.oldest);
+        assert!((result.mean - expected.mean).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_aggregators_empty_and_accumulate() {
+    let aggregator = (Count, Sum::of(|x: &i32| *x), Max::of(|x: &i32| *x));
+
+    let empty: Vec<i32> = vec![];
+    assert_eq!(
+        aggregator.finish(aggregator.accumulate(empty.iter())),
+        (0, 0, None)
+    );
+
+    let mut state = aggregator.accumulate([3, -1].iter());
+    aggregator.merge(&mut state, aggregator.accumulate([7].iter()));
+    assert_eq!(aggregator.finish(state), (3, 9, Some(7)));
+
+    let mean = Mean::of(|x: &i32| *x as f64);
+    assert!(mean.finish(mean.init()).is_nan());
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{
+    dataframe::DataFrame,
+    functions::{avg, count, sum},
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Order {
+    customer: String,
+    amount: f64,
+}
+
+fn orders() -> DataFrame<Order> {
+    DataFrame::new(
+        [
+            ("ana", 10.0),
+            ("bob", 5.0),
+            ("ana", 20.0),
+            ("carl", 100.0),
+            ("bob", 1.0),
+            ("ana", 30.0),
+        ]
+        .iter()
+        .map(|(customer, amount)| Order {
+            customer: customer.to_string(),
+            amount: *amount,
+        })
+        .collect(),
+    )
+}
+
+fn customers(df: &DataFrame<Order>) -> Vec<String> {
+    df.take(df.len()).into_iter().map(|o| o.customer).collect()
+}
+
+#[test]
+fn test_filter_groups_by_count() {
+    let df = orders()
+        .groupby(|o| o.customer.clone())
+        .filter_groups(|_, g| count(g) >= 2);
+
+    assert_eq!(customers(&df), vec!["ana", "bob", "ana", "bob", "ana"]);
+}
+
+#[test]
+fn test_filter_groups_by_aggregate_and_key() {
+    let df = orders()
+        .groupby(|o| o.customer.clone())
+        .filter_groups(|_, g| sum(g, |o| o.amount) > 50.0);
+    assert_eq!(customers(&df), vec!["ana", "ana", "carl", "ana"]);
+
+    let df = orders()
+        .groupby(|o| o.customer.clone())
+        .filter_groups(|customer, g| {
+            customer != "carl" && avg::<_, f64, _>(g, |o| o.amount) < 10.0
+        });
+    assert_eq!(customers(&df), vec!["bob", "bob"]);
+}
+
+#[test]
+fn test_filter_groups_keeps_all_or_none() {
+    let all = orders().groupby(|_| 0).filter_groups(|_, _| true);
+    assert_eq!(all.take(6), orders().take(6));
+
+    let none = orders()
+        .groupby(|o| o.customer.clone())
+        .filter_groups(|_, _| false);
+    assert_eq!(none.len(), 0);
+}
@@ -1,6 +1,6 @@
 use combee::{
     dataframe::DataFrame,
-    functions::{all, max, min, vec},
+    functions::{all, argmax, argmin, max, min, partial_max, partial_min, vec, NanPolicy},
 };
 use serde::{Deserialize, Serialize};
 
@@ -34,3 +34,112 @@ fn test_functions_vec() {
     assert_eq!(vec[1], 2);
     assert_eq!(vec[2], 13);
 }
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct F {
+    name: String,
+    value: f64,
+}
+
+fn floats(values: &[f64]) -> DataFrame<F> {
+    DataFrame::new(
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| F {
+                name: format!("row{}", i),
+                value: *v,
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_functions_partial_max_min() {
+    let df = floats(&[2.5, -1.0, 7.25, 0.0]);
+
+    let result = df
+        .groupby(all)
+        .agg(|_, g| {
+            (
+                partial_max(g, NanPolicy::Error, |x| x.value).unwrap(),
+                partial_min(g, NanPolicy::Error, |x| x.value).unwrap(),
+            )
+        })
+        .take(1);
+
+    assert_eq!(result[0], (Some(7.25), Some(-1.0)));
+}
+
+#[test]
+fn test_functions_nan_policy() {
+    let df = floats(&[2.5, f64::NAN, 7.25]);
+
+    let result = df
+        .groupby(all)
+        .agg(|_, g| {
+            (
+                partial_max(g, NanPolicy::Skip, |x| x.value).unwrap(),
+                partial_min(g, NanPolicy::Propagate, |x| x.value)
+                    .unwrap()
+                    .map(|v| v.is_nan()),
+                partial_max(g, NanPolicy::Error, |x| x.value).is_err(),
+            )
+        })
+        .take(1);
+    assert_eq!(result[0], (Some(7.25), Some(true), true));
+
+    let nans = floats(&[f64::NAN, f64::NAN]);
+    let result = nans
+        .groupby(all)
+        .agg(|_, g| partial_min(g, NanPolicy::Skip, |x| x.value).unwrap())
+        .take(1);
+    assert_eq!(result[0], None);
+}
+
+#[test]
+fn test_functions_argmax_argmin() {
+    let df = floats(&[2.5, 7.25, -1.0, 7.25, -1.0]);
+
+    let result = df
+        .groupby(all)
+        .agg(|_, g| {
+            (
+                argmax(g, NanPolicy::Skip, |x| x.value)
+                    .unwrap()
+                    .unwrap()
+                    .name
+                    .clone(),
+                argmin(g, NanPolicy::Skip, |x| x.value)
+                    .unwrap()
+                    .unwrap()
+                    .name
+                    .clone(),
+            )
+        })
+        .take(1);
+
+    // The first row is used on ties.
+    assert_eq!(result[0], ("row1".to_string(), "row2".to_string()));
+
+    let df = floats(&[1.0, f64::NAN, 3.0]);
+    let result = df
+        .groupby(all)
+        .agg(|_, g| {
+            (
+                argmax(g, NanPolicy::Skip, |x| x.value)
+                    .unwrap()
+                    .unwrap()
+                    .name
+                    .clone(),
+                argmax(g, NanPolicy::Propagate, |x| x.value)
+                    .unwrap()
+                    .unwrap()
+                    .name
+                    .clone(),
+                argmin(g, NanPolicy::Error, |x| x.value).is_err(),
+            )
+        })
+        .take(1);
+    assert_eq!(result[0], ("row2".to_string(), "row1".to_string(), true));
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{
+    dataframe::DataFrame,
+    functions::{count_where, first, last, nth, nunique},
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Event {
+    user: String,
+    kind: String,
+    time: u32,
+}
+
+fn events() -> DataFrame<Event> {
+    DataFrame::new(
+        [
+            ("ana", "click", 1),
+            ("bob", "view", 2),
+            ("ana", "view", 3),
+            ("ana", "click", 4),
+            ("bob", "view", 5),
+            ("carl", "buy", 6),
+        ]
+        .iter()
+        .map(|(user, kind, time)| Event {
+            user: user.to_string(),
+            kind: kind.to_string(),
+            time: *time,
+        })
+        .collect(),
+    )
+}
+
+#[test]
+fn test_first_last_latest_record_per_key() {
+    let latest = events()
+        .groupby(|e| e.user.clone())
+        .agg(|_, g| last(g).unwrap().clone());
+
+    let times: Vec<(String, u32)> = latest
+        .take(3)
+        .into_iter()
+        .map(|e| (e.user, e.time))
+        .collect();
+    assert_eq!(
+        times,
+        vec![
+            ("ana".to_string(), 4),
+            ("bob".to_string(), 5),
+            ("carl".to_string(), 6)
+        ]
+    );
+
+    let firsts = events()
+        .groupby(|e| e.user.clone())
+        .agg(|_, g| first(g).unwrap().time);
+    assert_eq!(firsts.take(3), vec![1, 2, 6]);
+}
+
+#[test]
+fn test_nth() {
+    let result = events()
+        .groupby(|e| e.user.clone())
+        .agg(|_, g| (nth(g, 0).map(|e| e.time), nth(g, 2).map(|e| e.time)));
+
+    assert_eq!(
+        result.take(3),
+        vec![(Some(1), Some(4)), (Some(2), None), (Some(6), None)]
+    );
+}
+
+#[test]
+fn test_nunique_and_count_where() {
+    let result = events().groupby(|e| e.user.clone()).agg(|user, g| {
+        (
+            user.clone(),
+            nunique(g, |e| e.kind.clone()),
+            count_where(g, |e| e.kind == "view"),
+        )
+    });
+
+    assert_eq!(
+        result.take(3),
+        vec![
+            ("ana".to_string(), 2, 1),
+            ("bob".to_string(), 1, 2),
+            ("carl".to_string(), 1, 0)
+        ]
+    );
+}
+
+#[test]
+fn test_deduplicate_events() {
+    let deduplicated = events()
+        .groupby(|e| (e.user.clone(), e.kind.clone()))
+        .agg(|_, g| first(g).unwrap().clone());
+
+    assert_eq!(deduplicated.len(), 4);
+    assert_eq!(
+        deduplicated
+            .take(4)
+            .iter()
+            .map(|e| e.time)
+            .collect::<Vec<u32>>(),
+        vec![1, 2, 3, 6]
+    );
+}
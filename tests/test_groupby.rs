@@ -204,3 +204,54 @@ fn test_groupby_all() {
     assert_eq!(row.1, 82.67 / 7.0);
     assert_eq!(row.2, 82.67)
 }
+
+#[test]
+fn test_groupby_first_seen_order() {
+    let df = combee::read_csv::<Data>(String::from("tests/fixtures/unsorted.csv")).unwrap();
+
+    for _ in 0..5 {
+        let df_grouped = df.groupby(|x| x.age).agg(|index, g| Stats {
+            index: *index,
+            value: count(g),
+        });
+
+        let indexes: Vec<u32> = df_grouped.take(4).iter().map(|s| s.index).collect();
+        assert_eq!(indexes, vec![26, 30, 22, 31]);
+    }
+}
+
+#[test]
+fn test_groupby_agg_sorted() {
+    let df = combee::read_csv::<Data>(String::from("tests/fixtures/unsorted.csv")).unwrap();
+    let df_grouped = df.groupby(|x| x.age).agg_sorted(|index, g| Stats {
+        index: *index,
+        value: count(g),
+    });
+
+    assert_eq!(
+        df_grouped.take(4),
+        vec![
+            Stats {
+                index: 22,
+                value: 2
+            },
+            Stats {
+                index: 26,
+                value: 2
+            },
+            Stats {
+                index: 30,
+                value: 1
+            },
+            Stats {
+                index: 31,
+                value: 1
+            },
+        ]
+    );
+
+    let names = df
+        .groupby(|x| x.name.chars().next().unwrap())
+        .agg_sorted(|index, _| *index);
+    assert_eq!(names.take(6), vec!['D', 'F', 'L', 'M', 'S']);
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{dataframe::DataFrame, functions::sum};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Sale {
+    store: String,
+    amount: u32,
+}
+
+fn sales() -> DataFrame<Sale> {
+    DataFrame::new(
+        [("b", 5), ("a", 1), ("b", 2), ("c", 7), ("a", 4)]
+            .iter()
+            .map(|(store, amount)| Sale {
+                store: store.to_string(),
+                amount: *amount,
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_groups_iteration() {
+    let df = sales();
+    let grouped = df.groupby(|s| s.store.clone());
+
+    let groups: Vec<(String, Vec<u32>)> = grouped
+        .groups()
+        .map(|(store, g)| (store, g.iter().map(|s| s.amount).collect()))
+        .collect();
+
+    assert_eq!(
+        groups,
+        vec![
+            ("b".to_string(), vec![5, 2]),
+            ("a".to_string(), vec![1, 4]),
+            ("c".to_string(), vec![7]),
+        ]
+    );
+}
+
+#[test]
+fn test_group_len_and_functions() {
+    let df = sales();
+    let grouped = df.groupby(|s| s.store.clone());
+
+    let sizes: Vec<usize> = grouped.groups().map(|(_, g)| g.len()).collect();
+    assert_eq!(sizes, vec![2, 2, 1]);
+    assert!(grouped.groups().all(|(_, g)| !g.is_empty()));
+
+    let totals: Vec<u32> = grouped
+        .groups()
+        .map(|(_, g)| sum(&g, |s| s.amount))
+        .collect();
+    assert_eq!(totals, vec![7, 5, 7]);
+}
+
+#[test]
+fn test_get_group_and_ngroups() {
+    let df = sales();
+    let grouped = df.groupby(|s| s.store.clone());
+
+    assert_eq!(grouped.ngroups(), 3);
+
+    let a = grouped.get_group(&"a".to_string()).unwrap();
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.take(2)[1].amount, 4);
+
+    assert!(grouped.get_group(&"z".to_string()).is_none());
+
+    let empty = DataFrame::<Sale>::new(vec![]);
+    assert_eq!(empty.groupby(|s| s.store.clone()).ngroups(), 0);
+}
+
+#[test]
+fn test_groups_reused_between_calls() {
+    let df = sales();
+    let grouped = df.groupby(|s| s.amount % 2);
+
+    let counts = grouped.agg(|index, g| (*index, g.len()));
+    assert_eq!(counts.take(2), vec![(1, 3), (0, 2)]);
+    assert_eq!(grouped.ngroups(), 2);
+
+    let evens = grouped.filter_groups(|index, _| *index == 0);
+    assert_eq!(evens.len(), 2);
+}
@@ -0,0 +1,231 @@
+use arrow2::io::ipc::read::read_file_metadata;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use time::{Date, Month};
+
+use combee::{
+    dataframe::DataFrame,
+    decimal::Decimal,
+    options::{IpcCompression, IpcFormat, IpcWriteOptions},
+    read_ipc, read_ipc_stream,
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Record {
+    id: u32,
+    name: String,
+    score: Option<f32>,
+    point: Point,
+    tags: Vec<String>,
+    price: Decimal,
+    day: Date,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Short {
+    id: u32,
+    name: String,
+}
+
+fn records(n: u32) -> DataFrame<Record> {
+    DataFrame::new(
+        (0..n)
+            .map(|i| Record {
+                id: i,
+                name: format!("record{}", i),
+                score: if i % 2 == 0 {
+                    Some(i as f32 / 2.0)
+                } else {
+                    None
+                },
+                point: Point {
+                    x: i as f64,
+                    y: -(i as f64),
+                },
+                tags: (0..i % 3).map(|t| t.to_string()).collect(),
+                price: Decimal::new(i as i128 * 125, 2),
+                day: Date::from_calendar_date(2023, Month::March, 1 + (i % 28) as u8).unwrap(),
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_ipc_file_round_trip() {
+    let df = records(10);
+    df.to_ipc(String::from("tmp/records.arrow")).unwrap();
+
+    let new_df = read_ipc::<Record>(String::from("tmp/records.arrow")).unwrap();
+    assert_eq!(new_df.take(10), df.take(10));
+}
+
+#[test]
+fn test_ipc_stream_compressed_round_trip() {
+    let df = records(25);
+
+    for (i, compression) in [
+        IpcCompression::Uncompressed,
+        IpcCompression::Lz4,
+        IpcCompression::Zstd,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let path = format!("tmp/records_stream_{}.arrow", i);
+        let options = IpcWriteOptions::new()
+            .format(IpcFormat::Stream)
+            .compression(compression);
+        df.to_ipc_with(path.clone(), &options).unwrap();
+
+        let new_df = read_ipc::<Record>(path).unwrap();
+        assert_eq!(new_df.take(25), df.take(25));
+    }
+}
+
+#[test]
+fn test_ipc_batches() {
+    let df = records(10);
+    let options = IpcWriteOptions::new()
+        .compression(IpcCompression::Zstd)
+        .batch_size(Some(4));
+    df.to_ipc_with(String::from("tmp/records_batches.arrow"), &options)
+        .unwrap();
+
+    let mut file = File::open("tmp/records_batches.arrow").unwrap();
+    let metadata = read_file_metadata(&mut file).unwrap();
+    assert_eq!(metadata.blocks.len(), 3);
+
+    let new_df = read_ipc::<Record>(String::from("tmp/records_batches.arrow")).unwrap();
+    assert_eq!(new_df.take(10), df.take(10));
+}
+
+#[test]
+fn test_ipc_slice_and_subset_of_columns() {
+    let df = records(10);
+    df.head(3)
+        .to_ipc(String::from("tmp/records_slice.arrow"))
+        .unwrap();
+
+    let new_df = read_ipc::<Short>(String::from("tmp/records_slice.arrow")).unwrap();
+    let ids: Vec<u32> = new_df.take(3).iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![0, 1, 2]);
+    assert_eq!(new_df.take(1)[0].name, "record0");
+}
+
+#[test]
+fn test_read_ipc_errors() {
+    assert!(read_ipc::<Record>(String::from("tmp/missing.arrow")).is_err());
+    assert!(read_ipc::<Record>(String::from("tests/fixtures/basic.csv")).is_err());
+
+    #[derive(Clone, Serialize, Deserialize, Debug)]
+    struct Wrong {
+        id: String,
+    }
+    records(2)
+        .to_ipc(String::from("tmp/records_wrong.arrow"))
+        .unwrap();
+    let message = read_ipc::<Wrong>(String::from("tmp/records_wrong.arrow"))
+        .err()
+        .unwrap()
+        .message;
+    assert!(!message.contains("Backtrace"));
+
+    let result = records(2).to_ipc_with(
+        String::from("tmp/records_zero_batch.arrow"),
+        &IpcWriteOptions::new().batch_size(Some(0)),
+    );
+    assert_eq!(
+        result.err().unwrap().message,
+        "Batch size must be greater than zero!"
+    );
+}
+
+#[test]
+fn test_ipc_empty_dataframe() {
+    let empty = DataFrame::<Record>::new(vec![]);
+    empty
+        .to_ipc(String::from("tmp/records_empty.arrow"))
+        .unwrap();
+    records(1)
+        .to_ipc(String::from("tmp/records_one.arrow"))
+        .unwrap();
+
+    // The schema is traced from the type, as it would be from the rows.
+    let schema = |path: &str| {
+        read_file_metadata(&mut File::open(path).unwrap())
+            .unwrap()
+            .schema
+    };
+    assert_eq!(
+        schema("tmp/records_empty.arrow"),
+        schema("tmp/records_one.arrow")
+    );
+    let new_df = read_ipc::<Record>(String::from("tmp/records_empty.arrow")).unwrap();
+    assert_eq!(new_df.len(), 0);
+
+    let mut buffer = Vec::new();
+    empty
+        .write_ipc_with(
+            &mut buffer,
+            &IpcWriteOptions::new().format(IpcFormat::Stream),
+        )
+        .unwrap();
+    assert_eq!(
+        read_ipc_stream::<Record, _>(buffer.as_slice())
+            .unwrap()
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_read_ipc_stream_without_seek() {
+    // A reader that can only be read once, as stdin or a pipe.
+    struct Pipe(std::collections::VecDeque<u8>);
+
+    impl std::io::Read for Pipe {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    let df = records(5);
+    let mut buffer = Vec::new();
+    df.write_ipc_with(
+        &mut buffer,
+        &IpcWriteOptions::new()
+            .format(IpcFormat::Stream)
+            .compression(IpcCompression::Lz4),
+    )
+    .unwrap();
+
+    let new_df = read_ipc_stream::<Record, _>(Pipe(buffer.into())).unwrap();
+    assert_eq!(new_df.take(5), df.take(5));
+}
+
+#[test]
+fn test_write_ipc_reports_flush_errors() {
+    struct FailingFlush;
+
+    impl std::io::Write for FailingFlush {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "disk full"))
+        }
+    }
+
+    for format in [IpcFormat::File, IpcFormat::Stream] {
+        let result =
+            records(3).write_ipc_with(FailingFlush, &IpcWriteOptions::new().format(format));
+        assert!(result.err().unwrap().message.contains("disk full"));
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{
+    dataframe::DataFrame,
+    functions::sum,
+    join::{Inner, Left, Outer, Right},
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Order {
+    user_id: u32,
+    amount: f64,
+}
+
+fn users() -> DataFrame<User> {
+    DataFrame::new(vec![
+        User {
+            id: 1,
+            name: "Daniel".to_string(),
+        },
+        User {
+            id: 2,
+            name: "Sergio".to_string(),
+        },
+        User {
+            id: 3,
+            name: "Ana".to_string(),
+        },
+    ])
+}
+
+fn orders() -> DataFrame<Order> {
+    DataFrame::new(vec![
+        Order {
+            user_id: 3,
+            amount: 1.0,
+        },
+        Order {
+            user_id: 1,
+            amount: 10.0,
+        },
+        Order {
+            user_id: 4,
+            amount: 7.5,
+        },
+        Order {
+            user_id: 1,
+            amount: 2.5,
+        },
+    ])
+}
+
+fn ids<A, B>(
+    rows: Vec<(A, B)>,
+    left: fn(&A) -> Option<u32>,
+    right: fn(&B) -> Option<f64>,
+) -> Vec<(Option<u32>, Option<f64>)> {
+    rows.iter().map(|(a, b)| (left(a), right(b))).collect()
+}
+
+#[test]
+fn test_inner_join() {
+    let df = users().join(&orders(), |u| u.id, |o| o.user_id, Inner);
+
+    assert_eq!(df.len(), 3);
+    let rows = ids(df.take(3), |u| Some(u.id), |o| Some(o.amount));
+    assert_eq!(
+        rows,
+        vec![
+            (Some(1), Some(10.0)),
+            (Some(1), Some(2.5)),
+            (Some(3), Some(1.0))
+        ]
+    );
+}
+
+#[test]
+fn test_left_join() {
+    let df = users().join(&orders(), |u| u.id, |o| o.user_id, Left);
+
+    let rows = ids(
+        df.take(10),
+        |u| Some(u.id),
+        |o| o.as_ref().map(|o| o.amount),
+    );
+    assert_eq!(
+        rows,
+        vec![
+            (Some(1), Some(10.0)),
+            (Some(1), Some(2.5)),
+            (Some(2), None),
+            (Some(3), Some(1.0))
+        ]
+    );
+}
+
+#[test]
+fn test_right_join() {
+    let df = users().join(&orders(), |u| u.id, |o| o.user_id, Right);
+
+    let rows = ids(
+        df.take(10),
+        |u| u.as_ref().map(|u| u.id),
+        |o| Some(o.amount),
+    );
+    assert_eq!(
+        rows,
+        vec![
+            (Some(1), Some(10.0)),
+            (Some(1), Some(2.5)),
+            (Some(3), Some(1.0)),
+            (None, Some(7.5))
+        ]
+    );
+}
+
+#[test]
+fn test_outer_join() {
+    let df = users().join(&orders(), |u| u.id, |o| o.user_id, Outer);
+
+    let rows = ids(
+        df.take(10),
+        |u| u.as_ref().map(|u| u.id),
+        |o| o.as_ref().map(|o| o.amount),
+    );
+    assert_eq!(
+        rows,
+        vec![
+            (Some(1), Some(10.0)),
+            (Some(1), Some(2.5)),
+            (Some(2), None),
+            (Some(3), Some(1.0)),
+            (None, Some(7.5))
+        ]
+    );
+}
+
+#[test]
+fn test_join_composite_key_and_groupby() {
+    let df = users().join(
+        &orders(),
+        |u| (u.id, u.id > 1),
+        |o| (o.user_id, o.user_id > 1),
+        Inner,
+    );
+
+    let totals = df
+        .groupby(|(u, _)| u.name.clone())
+        .agg(|name, g| (name.clone(), sum(g, |(_, o)| o.amount)));
+    assert_eq!(totals.len(), 2);
+    assert_eq!(totals.find(|(n, _)| n == "Daniel").unwrap().1, 12.5);
+
+    let empty = DataFrame::<Order>::new(vec![]);
+    assert_eq!(
+        users().join(&empty, |u| u.id, |o| o.user_id, Inner).len(),
+        0
+    );
+    assert_eq!(users().join(&empty, |u| u.id, |o| o.user_id, Left).len(), 3);
+}
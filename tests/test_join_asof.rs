@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+use combee::{dataframe::DataFrame, join::AsofDirection};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Trade {
+    symbol: String,
+    time: i64,
+    price: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Quote {
+    symbol: String,
+    time: i64,
+    bid: f64,
+}
+
+fn trade(symbol: &str, time: i64, price: f64) -> Trade {
+    Trade {
+        symbol: symbol.to_string(),
+        time,
+        price,
+    }
+}
+
+fn quote(symbol: &str, time: i64, bid: f64) -> Quote {
+    Quote {
+        symbol: symbol.to_string(),
+        time,
+        bid,
+    }
+}
+
+fn trades() -> DataFrame<Trade> {
+    DataFrame::new(vec![
+        trade("A", 1, 10.0),
+        trade("A", 5, 10.5),
+        trade("B", 6, 20.0),
+        trade("A", 10, 11.0),
+        trade("B", 15, 21.0),
+    ])
+}
+
+fn quotes() -> DataFrame<Quote> {
+    DataFrame::new(vec![
+        quote("A", 2, 9.9),
+        quote("B", 3, 19.9),
+        quote("A", 4, 10.4),
+        quote("A", 4, 10.45),
+        quote("B", 9, 20.5),
+        quote("A", 12, 11.1),
+    ])
+}
+
+fn bids(df: DataFrame<(Trade, Option<Quote>)>) -> Vec<Option<f64>> {
+    df.take(df.len())
+        .into_iter()
+        .map(|(_, q)| q.map(|q| q.bid))
+        .collect()
+}
+
+#[test]
+fn test_join_asof_directions() {
+    let backward = trades().join_asof(
+        &quotes(),
+        |t| t.time,
+        |q| q.time,
+        AsofDirection::Backward,
+        None::<i64>,
+    );
+    // Without a by key the trade of B at 6 uses the quote of A at 4, the last of the equal keys.
+    assert_eq!(
+        bids(backward),
+        vec![None, Some(10.45), Some(10.45), Some(20.5), Some(11.1)]
+    );
+
+    let forward = trades().join_asof(
+        &quotes(),
+        |t| t.time,
+        |q| q.time,
+        AsofDirection::Forward,
+        None::<i64>,
+    );
+    assert_eq!(
+        bids(forward),
+        vec![Some(9.9), Some(20.5), Some(20.5), Some(11.1), None]
+    );
+
+    let nearest = trades().join_asof(
+        &quotes(),
+        |t| t.time,
+        |q| q.time,
+        AsofDirection::Nearest,
+        None::<i64>,
+    );
+    assert_eq!(
+        bids(nearest),
+        vec![Some(9.9), Some(10.45), Some(10.45), Some(20.5), Some(11.1)]
+    );
+}
+
+#[test]
+fn test_join_asof_by_and_tolerance() {
+    let df = trades().join_asof_by(
+        &quotes(),
+        |t| t.symbol.clone(),
+        |q| q.symbol.clone(),
+        |t| t.time,
+        |q| q.time,
+        AsofDirection::Backward,
+        Some(5),
+    );
+
+    // A at 10 and B at 15 only have quotes of their symbol older than the tolerance.
+    assert_eq!(bids(df), vec![None, Some(10.45), Some(19.9), None, None]);
+
+    let df = trades().join_asof_by(
+        &quotes(),
+        |t| t.symbol.clone(),
+        |q| q.symbol.clone(),
+        |t| t.time,
+        |q| q.time,
+        AsofDirection::Nearest,
+        Some(2),
+    );
+    assert_eq!(
+        bids(df),
+        vec![Some(9.9), Some(10.45), None, Some(11.1), None]
+    );
+}
+
+#[test]
+fn test_join_asof_unsorted_and_float_keys() {
+    let points = DataFrame::new(vec![0.9, 2.6, -1.0]);
+    let grid = DataFrame::new(vec![2.0, 0.0, 1.0, 3.0]);
+
+    let df = points.join_asof(&grid, |p| *p, |g| *g, AsofDirection::Nearest, None);
+    let rows: Vec<Option<f64>> = df.take(3).into_iter().map(|(_, g)| g).collect();
+    assert_eq!(rows, vec![Some(1.0), Some(3.0), Some(0.0)]);
+
+    let df = points.join_asof(&grid, |p| *p, |g| *g, AsofDirection::Backward, Some(0.5));
+    let rows: Vec<Option<f64>> = df.take(3).into_iter().map(|(_, g)| g).collect();
+    assert_eq!(rows, vec![None, None, None]);
+}
+
+#[test]
+fn test_join_asof_datetimes() {
+    let start = OffsetDateTime::UNIX_EPOCH;
+    let events = DataFrame::new(vec![
+        (1, start + Duration::minutes(3)),
+        (2, start + Duration::hours(2)),
+    ]);
+    let readings = DataFrame::new(vec![(start, 1.5), (start + Duration::minutes(1), 2.5)]);
+
+    let df = events.join_asof(
+        &readings,
+        |e| e.1,
+        |r| r.0,
+        AsofDirection::Backward,
+        Some(Duration::minutes(30)),
+    );
+    let rows = df.take(2);
+    assert_eq!(rows[0].1.map(|r| r.1), Some(2.5));
+    assert_eq!(rows[1].1, None);
+}
+
+#[test]
+fn test_join_asof_nan_and_extreme_keys() {
+    // NaN keys never match, on either side.
+    let points = DataFrame::new(vec![1.2, f64::NAN, 2.9]);
+    let grid = DataFrame::new(vec![f64::NAN, 3.0, 1.0, f64::NAN, 2.0]);
+    let df = points.join_asof(&grid, |p| *p, |g| *g, AsofDirection::Backward, None);
+    let rows: Vec<Option<f64>> = df.take(3).into_iter().map(|(_, g)| g).collect();
+    assert_eq!(rows, vec![Some(1.0), None, Some(2.0)]);
+
+    // Distances that overflow the key type are outside the tolerance instead of panicking.
+    let left = DataFrame::new(vec![i64::MAX, 0]);
+    let right = DataFrame::new(vec![i64::MIN, 5]);
+    let df = left.join_asof(&right, |l| *l, |r| *r, AsofDirection::Nearest, Some(10));
+    let rows: Vec<Option<i64>> = df.take(2).into_iter().map(|(_, r)| r).collect();
+    assert_eq!(rows, vec![None, Some(5)]);
+
+    let df = left.join_asof(&right, |l| *l, |r| *r, AsofDirection::Nearest, None);
+    let rows: Vec<Option<i64>> = df.take(2).into_iter().map(|(_, r)| r).collect();
+    assert_eq!(rows, vec![Some(5), Some(5)]);
+}
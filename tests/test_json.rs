@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{
+    dataframe::DataFrame, read_json, read_json_from_reader, read_ndjson, read_ndjson_from_reader,
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Address {
+    city: String,
+    zip: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Person {
+    name: String,
+    age: u32,
+    address: Address,
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_read_json_nested() {
+    let df = read_json::<Person>(String::from("tests/fixtures/people.json")).unwrap();
+
+    assert_eq!(df.len(), 2);
+    let rows = df.take(2);
+    assert_eq!(rows[0].address.city, "Madrid");
+    assert_eq!(rows[0].address.zip, Some("28001".to_string()));
+    assert_eq!(rows[0].tags, vec!["a", "b"]);
+    assert_eq!(rows[1].address.zip, None);
+}
+
+#[test]
+fn test_read_ndjson_skips_blank_lines() {
+    let df = read_ndjson::<Person>(String::from("tests/fixtures/people.ndjson")).unwrap();
+    let expected = read_json::<Person>(String::from("tests/fixtures/people.json")).unwrap();
+
+    assert_eq!(df.take(2), expected.take(2));
+}
+
+#[test]
+fn test_read_json_errors_report_lines() {
+    let result = read_ndjson::<Person>(String::from("tests/fixtures/invalid.ndjson"));
+    let message = result.err().unwrap().message;
+    assert!(
+        message.starts_with("Error parsing NDJSON at line 2"),
+        "{}",
+        message
+    );
+
+    let json = "[\n{\"name\": \"Daniel\", \"age\": 26},\n{\"name\": \"Sergio\", \"age\": -1}\n]";
+    #[derive(Clone, Serialize, Deserialize, Debug)]
+    struct Short {
+        name: String,
+        age: u32,
+    }
+    let result = read_json_from_reader::<Short, _>(json.as_bytes());
+    let message = result.err().unwrap().message;
+    assert!(
+        message.starts_with("Error parsing JSON at line 3"),
+        "{}",
+        message
+    );
+
+    assert!(read_json::<Person>(String::from("tests/fixtures/missing.json")).is_err());
+}
+
+#[test]
+fn test_to_json_round_trip() {
+    let df = read_json::<Person>(String::from("tests/fixtures/people.json")).unwrap();
+
+    df.to_json(String::from("tmp/people.json")).unwrap();
+    let json = read_json::<Person>(String::from("tmp/people.json")).unwrap();
+    assert_eq!(json.take(2), df.take(2));
+
+    df.to_ndjson(String::from("tmp/people.ndjson")).unwrap();
+    let ndjson = read_ndjson::<Person>(String::from("tmp/people.ndjson")).unwrap();
+    assert_eq!(ndjson.take(2), df.take(2));
+}
+
+#[test]
+fn test_write_json_format() {
+    let df = DataFrame::new(vec![(1, "a".to_string()), (2, "b".to_string())]);
+
+    let mut buffer = Vec::new();
+    df.write_ndjson(&mut buffer).unwrap();
+    assert_eq!(
+        String::from_utf8(buffer.clone()).unwrap(),
+        "[1,\"a\"]\n[2,\"b\"]\n"
+    );
+    let rows = read_ndjson_from_reader::<(i32, String), _>(buffer.as_slice()).unwrap();
+    assert_eq!(rows.take(2), df.take(2));
+
+    let mut buffer = Vec::new();
+    DataFrame::<(i32, String)>::new(vec![])
+        .write_json(&mut buffer)
+        .unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), "[]\n");
+}
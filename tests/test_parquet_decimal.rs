@@ -0,0 +1,278 @@
+use parquet::{
+    basic::LogicalType,
+    file::reader::{FileReader, SerializedFileReader},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fs::File};
+
+use combee::{dataframe::DataFrame, decimal::Decimal, options::ParquetWriteOptions, read_parquet};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Trade {
+    id: i32,
+    price: Decimal,
+    fee: String,
+    amount: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct TradeFloat {
+    id: i32,
+    price: f64,
+    fee: f64,
+    amount: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct TradeString {
+    id: i32,
+    price: String,
+    fee: String,
+    amount: String,
+}
+
+fn trades() -> DataFrame<Trade> {
+    DataFrame::new(vec![
+        Trade {
+            id: 1,
+            price: Decimal::new(12345, 2),
+            fee: "0.015".to_string(),
+            amount: 10.0,
+        },
+        Trade {
+            id: 2,
+            price: Decimal::new(-50, 2),
+            fee: "1.1".to_string(),
+            amount: 2.25,
+        },
+        Trade {
+            id: 3,
+            price: Decimal::new(99999999, 2),
+            fee: "0".to_string(),
+            amount: -0.125,
+        },
+    ])
+}
+
+fn decimal_options() -> ParquetWriteOptions {
+    ParquetWriteOptions::new()
+        .decimal("price", 10, 2)
+        .decimal("fee", 25, 3)
+        .decimal("amount", 6, 3)
+}
+
+#[test]
+fn test_decimal_parse_and_display() {
+    let d: Decimal = "-0.05".parse().unwrap();
+    assert_eq!(d, Decimal::new(-5, 2));
+    assert_eq!(d.to_string(), "-0.05");
+
+    assert_eq!("42".parse::<Decimal>().unwrap(), Decimal::new(42, 0));
+    assert_eq!(Decimal::new(1255, 3).rescale(2), Some(Decimal::new(126, 2)));
+    assert_eq!(
+        Decimal::new(-1255, 3).rescale(2),
+        Some(Decimal::new(-126, 2))
+    );
+    assert_eq!(Decimal::new(7, 0).rescale(2), Some(Decimal::new(700, 2)));
+    // Rounding a remainder close to the factor does not overflow.
+    assert_eq!(
+        Decimal::new(9 * 10i128.pow(37), 38).rescale(0),
+        Some(Decimal::new(1, 0))
+    );
+
+    assert!("1.2.3".parse::<Decimal>().is_err());
+    assert!("abc".parse::<Decimal>().is_err());
+}
+
+#[test]
+fn test_to_parquet_decimal_schema() {
+    trades()
+        .to_parquet_with(
+            String::from("tmp/decimal_schema.parquet"),
+            &decimal_options(),
+        )
+        .unwrap();
+
+    let reader =
+        SerializedFileReader::new(File::open("tmp/decimal_schema.parquet").unwrap()).unwrap();
+    let columns = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .to_vec();
+
+    let decimals: Vec<Option<LogicalType>> =
+        columns[1..].iter().map(|c| c.logical_type()).collect();
+    assert_eq!(
+        decimals,
+        vec![
+            Some(LogicalType::Decimal {
+                scale: 2,
+                precision: 10
+            }),
+            Some(LogicalType::Decimal {
+                scale: 3,
+                precision: 25
+            }),
+            Some(LogicalType::Decimal {
+                scale: 3,
+                precision: 6
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_read_parquet_decimal_round_trip() {
+    trades()
+        .to_parquet_with(
+            String::from("tmp/decimal_round_trip.parquet"),
+            &decimal_options(),
+        )
+        .unwrap();
+
+    let df = read_parquet::<Trade>(String::from("tmp/decimal_round_trip.parquet")).unwrap();
+    let rows = df.take(3);
+
+    assert_eq!(rows[0].price, Decimal::new(12345, 2));
+    assert_eq!(rows[1].price, Decimal::new(-50, 2));
+    assert_eq!(rows[2].price, Decimal::new(99999999, 2));
+    assert_eq!(rows[0].fee, "0.015");
+    assert_eq!(rows[1].fee, "1.100");
+    assert_eq!(rows[1].amount, 2.25);
+    assert_eq!(rows[2].amount, -0.125);
+}
+
+#[test]
+fn test_read_parquet_decimal_as_float_and_string() {
+    trades()
+        .to_parquet_with(
+            String::from("tmp/decimal_types.parquet"),
+            &decimal_options(),
+        )
+        .unwrap();
+
+    let df = read_parquet::<TradeFloat>(String::from("tmp/decimal_types.parquet")).unwrap();
+    let row = df.find(|t| t.id == 1).unwrap();
+    assert_eq!(row.price, 123.45);
+    assert_eq!(row.fee, 0.015);
+
+    let df = read_parquet::<TradeString>(String::from("tmp/decimal_types.parquet")).unwrap();
+    let row = df.find(|t| t.id == 2).unwrap();
+    assert_eq!(row.price, "-0.50");
+    assert_eq!(row.amount, "2.250");
+}
+
+#[test]
+fn test_to_parquet_decimal_errors() {
+    let result = trades().to_parquet_with(
+        String::from("tmp/decimal_errors.parquet"),
+        &ParquetWriteOptions::new().decimal("price", 4, 2),
+    );
+    assert!(result.is_err());
+
+    let result = trades().to_parquet_with(
+        String::from("tmp/decimal_errors.parquet"),
+        &ParquetWriteOptions::new().decimal("price", 39, 2),
+    );
+    assert!(result.is_err());
+
+    let result = trades().to_parquet_with(
+        String::from("tmp/decimal_errors.parquet"),
+        &ParquetWriteOptions::new().decimal("unknown", 10, 2),
+    );
+    assert!(result.is_err());
+    // No truncated file is left behind.
+    assert!(!std::path::Path::new("tmp/decimal_errors.parquet").exists());
+}
+
+#[test]
+fn test_decimal_compares_by_value() {
+    assert_eq!(Decimal::new(125, 1), Decimal::new(1250, 2));
+    assert_ne!(Decimal::new(125, 1), Decimal::new(1251, 2));
+    assert!(Decimal::new(-5, 1) < Decimal::new(-49, 2));
+
+    let values: HashSet<Decimal> = ["12.5", "12.50", "12.500", "-0", "0.0"]
+        .iter()
+        .map(|v| v.parse().unwrap())
+        .collect();
+    assert_eq!(values.len(), 2);
+}
+
+#[test]
+fn test_decimal_compares_scales_above_38() {
+    let zero = Decimal::new(0, 0);
+    let tiny = Decimal::new(1, 40);
+    assert_eq!(zero, Decimal::new(0, 40));
+    assert_eq!(Decimal::new(0, 40), zero);
+    assert!(zero < tiny);
+    assert!(Decimal::new(-1, 40) < zero);
+    assert!(tiny < Decimal::new(1, 39));
+    assert!(Decimal::new(-1, 39) < Decimal::new(-1, 40));
+    assert_eq!(Decimal::new(10, 41), tiny);
+    assert!(Decimal::new(1, 0) > Decimal::new(i128::MAX, 60));
+    assert!(Decimal::new(i128::MAX, 0) > Decimal::new(1, 50));
+
+    let values: HashSet<Decimal> = [zero, Decimal::new(0, 40), tiny, Decimal::new(100, 42)]
+        .into_iter()
+        .collect();
+    assert_eq!(values.len(), 2);
+}
+
+#[test]
+fn test_to_parquet_decimal_by_default() {
+    #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+    struct Order {
+        id: i32,
+        price: Decimal,
+        discount: Option<Decimal>,
+    }
+
+    let df = DataFrame::new(vec![
+        Order {
+            id: 1,
+            price: Decimal::new(12345, 2),
+            discount: None,
+        },
+        Order {
+            id: 2,
+            price: Decimal::new(-5, 1),
+            discount: Some(Decimal::new(1, 3)),
+        },
+        Order {
+            id: 3,
+            price: Decimal::new(99999999, 2),
+            discount: Some(Decimal::new(25, 0)),
+        },
+    ]);
+    df.to_parquet(String::from("tmp/decimal_default.parquet"))
+        .unwrap();
+
+    let reader =
+        SerializedFileReader::new(File::open("tmp/decimal_default.parquet").unwrap()).unwrap();
+    let types: Vec<Option<LogicalType>> = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|c| c.logical_type())
+        .collect();
+    assert_eq!(
+        types[1..],
+        [
+            Some(LogicalType::Decimal {
+                scale: 2,
+                precision: 8
+            }),
+            Some(LogicalType::Decimal {
+                scale: 3,
+                precision: 5
+            }),
+        ]
+    );
+
+    let read = read_parquet::<Order>(String::from("tmp/decimal_default.parquet")).unwrap();
+    assert_eq!(read.take(3), df.take(3));
+}
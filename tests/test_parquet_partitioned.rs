@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use combee::{
+    dataframe::DataFrame,
+    options::{ParquetReadOptions, ParquetWriteOptions},
+    read_parquet, read_parquet_partitioned, read_parquet_partitioned_with,
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Sale {
+    id: i32,
+    region: String,
+    year: i32,
+    amount: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Amount {
+    id: i32,
+    amount: f64,
+}
+
+#[derive(Serialize)]
+struct Key {
+    region: String,
+    year: i32,
+}
+
+fn sales() -> DataFrame<Sale> {
+    DataFrame::new(
+        (0..12)
+            .map(|i| Sale {
+                id: i,
+                region: if i % 2 == 0 { "north/east" } else { "south" }.to_string(),
+                year: 2020 + i % 3,
+                amount: i as f64 * 1.5,
+            })
+            .collect(),
+    )
+}
+
+fn clean(root: &str) -> String {
+    let _ = fs::remove_dir_all(root);
+    root.to_string()
+}
+
+fn key(sale: &Sale) -> Key {
+    Key {
+        region: sale.region.clone(),
+        year: sale.year,
+    }
+}
+
+#[test]
+fn test_to_parquet_partitioned_layout() {
+    sales()
+        .to_parquet_partitioned(clean("tmp/partitioned_layout"), key)
+        .unwrap();
+
+    let part = Path::new("tmp/partitioned_layout/region=south/year=2021/part-00000.parquet");
+    assert!(part.exists());
+    assert!(Path::new("tmp/partitioned_layout/region=north%2Feast/year=2020").is_dir());
+
+    // Partition columns are only stored in the directory names.
+    let df = read_parquet::<Amount>(part.to_str().unwrap().to_string()).unwrap();
+    let ids: Vec<i32> = df.apply(|a| a.id).take(10);
+    assert_eq!(ids, vec![1, 7]);
+    assert!(read_parquet::<Sale>(part.to_str().unwrap().to_string()).is_err());
+}
+
+#[test]
+fn test_read_parquet_partitioned_round_trip() {
+    sales()
+        .to_parquet_partitioned(clean("tmp/partitioned_round_trip"), key)
+        .unwrap();
+
+    let df = read_parquet_partitioned::<Sale>(String::from("tmp/partitioned_round_trip")).unwrap();
+    let mut rows = df.take(12);
+    rows.sort_by_key(|s| s.id);
+
+    assert_eq!(rows, sales().take(12));
+}
+
+#[test]
+fn test_read_parquet_partitioned_parallel() {
+    sales()
+        .to_parquet_partitioned(clean("tmp/partitioned_parallel"), key)
+        .unwrap();
+
+    let options = ParquetReadOptions::new().parallel(true);
+    let df =
+        read_parquet_partitioned_with::<Sale>(String::from("tmp/partitioned_parallel"), &options)
+            .unwrap();
+
+    assert_eq!(df.len(), 12);
+    assert!(df
+        .find(|s| s.region == "north/east" && s.year == 2022)
+        .is_some());
+}
+
+#[test]
+fn test_to_parquet_partitioned_appends_and_nulls() {
+    #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+    struct Event {
+        id: i32,
+        kind: Option<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Kind {
+        kind: Option<String>,
+    }
+
+    let df = DataFrame::new(vec![
+        Event {
+            id: 1,
+            kind: Some("click".to_string()),
+        },
+        Event { id: 2, kind: None },
+    ]);
+
+    clean("tmp/partitioned_nulls");
+    for _ in 0..2 {
+        df.to_parquet_partitioned(String::from("tmp/partitioned_nulls"), |e| Kind {
+            kind: e.kind.clone(),
+        })
+        .unwrap();
+    }
+
+    assert!(Path::new("tmp/partitioned_nulls/kind=click/part-00001.parquet").exists());
+    assert!(Path::new("tmp/partitioned_nulls/kind=__HIVE_DEFAULT_PARTITION__").is_dir());
+
+    let df = read_parquet_partitioned::<Event>(String::from("tmp/partitioned_nulls")).unwrap();
+    assert_eq!(df.len(), 4);
+    assert_eq!(df.find(|e| e.id == 2).unwrap().kind, None);
+}
+
+#[test]
+fn test_to_parquet_partitioned_empty_string() {
+    #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+    struct Event {
+        id: i32,
+        kind: String,
+    }
+
+    #[derive(Serialize)]
+    struct Kind {
+        kind: String,
+    }
+
+    let df = DataFrame::new(vec![
+        Event {
+            id: 1,
+            kind: "click".to_string(),
+        },
+        Event {
+            id: 2,
+            kind: "".to_string(),
+        },
+    ]);
+
+    let root = clean("tmp/partitioned_empty");
+    df.to_parquet_partitioned(root.clone(), |e| Kind {
+        kind: e.kind.clone(),
+    })
+    .unwrap();
+    assert!(Path::new("tmp/partitioned_empty/kind=/part-00000.parquet").exists());
+
+    let read = read_parquet_partitioned::<Event>(root).unwrap();
+    assert_eq!(read.find(|e| e.id == 2).unwrap().kind, "");
+}
+
+#[test]
+fn test_to_parquet_partitioned_removes_failed_files() {
+    let root = clean("tmp/partitioned_failed");
+    let options = ParquetWriteOptions::new().decimal("amount", 2, 1);
+    let result = sales().to_parquet_partitioned_with(root.clone(), key, &options);
+    assert!(result.is_err());
+
+    // The partitions written before the error are kept, without a truncated file.
+    let read = read_parquet_partitioned::<Sale>(root).unwrap();
+    assert!(read.len() < 12);
+}
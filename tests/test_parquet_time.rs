@@ -0,0 +1,271 @@
+use parquet::{
+    basic::{LogicalType, TimeUnit},
+    file::reader::{FileReader, SerializedFileReader},
+};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use combee::{dataframe::DataFrame, read_parquet};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Event {
+    id: i32,
+    #[serde(with = "combee::temporal::date")]
+    day: Date,
+    #[serde(with = "combee::temporal::primitive_date_time")]
+    local: PrimitiveDateTime,
+    #[serde(with = "combee::temporal::offset_date_time")]
+    instant: OffsetDateTime,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Raw {
+    id: i32,
+    day: u32,
+    local: u64,
+}
+
+fn date(year: i32, month: Month, day: u8) -> Date {
+    Date::from_calendar_date(year, month, day).unwrap()
+}
+
+fn events() -> DataFrame<Event> {
+    DataFrame::new(vec![
+        Event {
+            id: 1,
+            day: date(2023, Month::January, 15),
+            local: PrimitiveDateTime::new(
+                date(2023, Month::January, 15),
+                Time::from_hms_micro(13, 45, 30, 123_456).unwrap(),
+            ),
+            instant: OffsetDateTime::UNIX_EPOCH,
+        },
+        Event {
+            id: 2,
+            day: date(1969, Month::July, 20),
+            local: PrimitiveDateTime::new(date(1969, Month::July, 20), Time::MIDNIGHT),
+            instant: PrimitiveDateTime::new(
+                date(2024, Month::February, 29),
+                Time::from_hms(23, 30, 0).unwrap(),
+            )
+            .assume_offset(UtcOffset::from_hms(-3, 0, 0).unwrap()),
+        },
+    ])
+}
+
+#[test]
+fn test_to_parquet_time_logical_types() {
+    events()
+        .to_parquet(String::from("tmp/time_schema.parquet"))
+        .unwrap();
+
+    let reader = SerializedFileReader::new(File::open("tmp/time_schema.parquet").unwrap()).unwrap();
+    let columns = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .to_vec();
+
+    let types: Vec<Option<LogicalType>> = columns.iter().map(|c| c.logical_type()).collect();
+    assert_eq!(
+        types[1..],
+        [
+            Some(LogicalType::Date),
+            Some(LogicalType::Timestamp {
+                is_adjusted_to_u_t_c: false,
+                unit: TimeUnit::MICROS(Default::default()),
+            }),
+            Some(LogicalType::Timestamp {
+                is_adjusted_to_u_t_c: true,
+                unit: TimeUnit::MICROS(Default::default()),
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_read_parquet_time_round_trip() {
+    events()
+        .to_parquet(String::from("tmp/time_round_trip.parquet"))
+        .unwrap();
+
+    let df = read_parquet::<Event>(String::from("tmp/time_round_trip.parquet")).unwrap();
+    let rows = df.take(2);
+    let expected = events().take(2);
+
+    assert_eq!(rows[0], expected[0]);
+    assert_eq!(rows[1].day, expected[1].day);
+    assert_eq!(rows[1].local, expected[1].local);
+    // Instants are read back in UTC.
+    assert_eq!(rows[1].instant, expected[1].instant);
+    assert_eq!(rows[1].instant.offset(), UtcOffset::UTC);
+    assert_eq!(rows[1].instant.day(), 1);
+}
+
+#[test]
+fn test_read_parquet_time_as_integers() {
+    events()
+        .to_parquet(String::from("tmp/time_integers.parquet"))
+        .unwrap();
+
+    let df = read_parquet::<Raw>(String::from("tmp/time_integers.parquet")).unwrap();
+    let row = df.find(|r| r.id == 1).unwrap();
+
+    assert_eq!(row.day, 19372);
+    assert_eq!(row.local, 1_673_790_330_123_456);
+}
+
+#[test]
+fn test_read_parquet_date_as_datetime() {
+    #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+    struct Midnight {
+        id: i32,
+        #[serde(with = "combee::temporal::primitive_date_time")]
+        day: PrimitiveDateTime,
+    }
+
+    events()
+        .to_parquet(String::from("tmp/time_midnight.parquet"))
+        .unwrap();
+
+    let df = read_parquet::<Midnight>(String::from("tmp/time_midnight.parquet")).unwrap();
+    let row = df.find(|r| r.id == 2).unwrap();
+
+    assert_eq!(
+        row.day,
+        PrimitiveDateTime::new(date(1969, Month::July, 20), Time::MIDNIGHT)
+    );
+}
+
+#[test]
+fn test_parquet_optional_time() {
+    #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+    struct Visit {
+        id: i32,
+        #[serde(with = "combee::temporal::option_date")]
+        day: Option<Date>,
+    }
+
+    let df = DataFrame::new(vec![
+        Visit { id: 1, day: None },
+        Visit {
+            id: 2,
+            day: Some(date(2023, Month::March, 1)),
+        },
+    ]);
+    df.to_parquet(String::from("tmp/time_optional.parquet"))
+        .unwrap();
+
+    let reader =
+        SerializedFileReader::new(File::open("tmp/time_optional.parquet").unwrap()).unwrap();
+    let column = reader.metadata().file_metadata().schema_descr().column(1);
+    assert_eq!(column.logical_type(), Some(LogicalType::Date));
+
+    let read = read_parquet::<Visit>(String::from("tmp/time_optional.parquet")).unwrap();
+    assert_eq!(read.take(2), df.take(2));
+
+    // Other formats keep the plain serde representation of `time`.
+    let json = serde_json::to_string(&df.take(2)).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Vec<Visit>>(&json).unwrap(),
+        df.take(2)
+    );
+}
+
+#[test]
+fn test_parquet_plain_tuple_is_not_time() {
+    #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+    struct Pair {
+        id: i32,
+        pair: (i32, u16),
+        day: Date,
+    }
+
+    // Without the serde helpers, fields that look like `time` components are written as they are.
+    let df = DataFrame::new(vec![
+        Pair {
+            id: 1,
+            pair: (7, 0),
+            day: date(2023, Month::January, 15),
+        },
+        Pair {
+            id: 2,
+            pair: (-3, 500),
+            day: date(1969, Month::July, 20),
+        },
+    ]);
+    df.to_parquet(String::from("tmp/time_plain_tuple.parquet"))
+        .unwrap();
+
+    let reader =
+        SerializedFileReader::new(File::open("tmp/time_plain_tuple.parquet").unwrap()).unwrap();
+    let types: Vec<Option<LogicalType>> = reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|c| c.logical_type())
+        .collect();
+    assert!(!types.contains(&Some(LogicalType::Date)));
+
+    let read = read_parquet::<Pair>(String::from("tmp/time_plain_tuple.parquet")).unwrap();
+    assert_eq!(read.take(2), df.take(2));
+}
+
+#[test]
+fn test_parquet_time_truncated_to_micros() {
+    let offset = UtcOffset::from_hms(5, 30, 0).unwrap();
+    let df = DataFrame::new(vec![
+        Event {
+            id: 1,
+            day: date(2023, Month::March, 1),
+            local: PrimitiveDateTime::new(
+                date(2023, Month::March, 1),
+                Time::from_hms_nano(8, 0, 0, 123_456_789).unwrap(),
+            ),
+            instant: PrimitiveDateTime::new(
+                date(2023, Month::March, 1),
+                Time::from_hms_nano(1, 2, 3, 999_999_999).unwrap(),
+            )
+            .assume_offset(offset),
+        },
+        Event {
+            id: 2,
+            day: date(1969, Month::December, 31),
+            local: PrimitiveDateTime::new(
+                date(1969, Month::December, 31),
+                Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap(),
+            ),
+            instant: OffsetDateTime::UNIX_EPOCH - time::Duration::nanoseconds(1),
+        },
+    ]);
+    df.to_parquet(String::from("tmp/time_nanos.parquet"))
+        .unwrap();
+
+    let rows = read_parquet::<Event>(String::from("tmp/time_nanos.parquet"))
+        .unwrap()
+        .take(2);
+
+    assert_eq!(rows[0].local.nanosecond(), 123_456_000);
+    // Instants keep their moment, truncated to micros, but are read back in UTC.
+    let instant = rows[0].instant;
+    assert_eq!(instant.offset(), UtcOffset::UTC);
+    assert_eq!(instant.hour(), 19);
+    assert_eq!(instant.day(), 28);
+    assert_eq!(instant.nanosecond(), 999_999_000);
+    assert_eq!(
+        instant,
+        df.take(1)[0].instant - time::Duration::nanoseconds(999)
+    );
+
+    // Values before epoch are truncated towards the past.
+    assert_eq!(rows[1].local.second(), 59);
+    assert_eq!(rows[1].local.nanosecond(), 999_999_000);
+    assert_eq!(
+        rows[1].instant,
+        OffsetDateTime::UNIX_EPOCH - time::Duration::microseconds(1)
+    );
+}
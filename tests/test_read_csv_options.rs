@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Index;
+
+use combee::{options::CsvReadOptions, read_csv_schema_with, read_csv_with};
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+struct Data {
+    name: String,
+    age: u32,
+}
+
+#[test]
+fn test_read_csv_semicolon() {
+    let options = CsvReadOptions::new().delimiter(b';');
+    let df = read_csv_with::<Data>(String::from("tests/fixtures/semicolon.csv"), &options).unwrap();
+
+    assert_eq!(df.len(), 3);
+
+    let result = df.take(3);
+    assert_eq!(
+        *result.index(1),
+        Data {
+            name: String::from("Sergio"),
+            age: 30
+        }
+    );
+}
+
+#[test]
+fn test_read_csv_headerless_with_comments() {
+    let options = CsvReadOptions::new()
+        .delimiter(b'\t')
+        .has_headers(false)
+        .comment(Some(b'#'));
+    let df =
+        read_csv_with::<Data>(String::from("tests/fixtures/headerless.tsv"), &options).unwrap();
+
+    assert_eq!(df.len(), 3);
+
+    let result = df.take(3);
+    assert_eq!(
+        *result.index(0),
+        Data {
+            name: String::from("Daniel"),
+            age: 26
+        }
+    );
+    assert_eq!(
+        *result.index(2),
+        Data {
+            name: String::from("Leticia"),
+            age: 22
+        }
+    );
+}
+
+#[test]
+fn test_read_csv_skip_rows() {
+    let options = CsvReadOptions::new().skip_rows(2);
+    let df = read_csv_with::<Data>(String::from("tests/fixtures/preamble.csv"), &options).unwrap();
+
+    assert_eq!(df.len(), 2);
+    assert_eq!(
+        *df.take(1).index(0),
+        Data {
+            name: String::from("Daniel"),
+            age: 26
+        }
+    );
+}
+
+#[test]
+fn test_read_csv_trim() {
+    let options = CsvReadOptions::new().trim(true);
+    let df = read_csv_with::<Data>(String::from("tests/fixtures/spaces.csv"), &options).unwrap();
+
+    assert_eq!(
+        df.take(2),
+        vec![
+            Data {
+                name: String::from("Daniel"),
+                age: 26
+            },
+            Data {
+                name: String::from("Sergio"),
+                age: 30
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_read_csv_schema_quoted_header() {
+    let columns = read_csv_schema_with(
+        String::from("tests/fixtures/quoted.csv"),
+        &CsvReadOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        columns,
+        [String::from("full name"), String::from("age, in years")].to_vec()
+    );
+}
+
+#[test]
+fn test_read_csv_schema_headerless() {
+    let options = CsvReadOptions::new()
+        .delimiter(b'\t')
+        .has_headers(false)
+        .comment(Some(b'#'));
+    let columns =
+        read_csv_schema_with(String::from("tests/fixtures/headerless.tsv"), &options).unwrap();
+    assert_eq!(columns, [String::from("0"), String::from("1")].to_vec());
+}
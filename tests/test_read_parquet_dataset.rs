@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use combee::{
+    dataframe::DataFrame, options::ParquetReadOptions, read_parquet_dataset,
+    read_parquet_dataset_with,
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Part {
+    id: i64,
+    name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Other {
+    id: String,
+    name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Reordered {
+    name: String,
+    id: Option<i64>,
+}
+
+fn write_dataset(dir: &str, parts: i64) {
+    fs::create_dir_all(dir).unwrap();
+    for p in 0..parts {
+        let df = DataFrame::new(
+            (0..3)
+                .map(|i| Part {
+                    id: p * 3 + i,
+                    name: format!("part{}", p),
+                })
+                .collect(),
+        );
+        df.to_parquet(format!("{}/part-{:05}.parquet", dir, p))
+            .unwrap();
+    }
+    fs::write(format!("{}/_SUCCESS", dir), "").unwrap();
+}
+
+#[test]
+fn test_read_parquet_dataset_directory() {
+    write_dataset("tmp/dataset_directory", 4);
+
+    let df = read_parquet_dataset::<Part>(String::from("tmp/dataset_directory")).unwrap();
+
+    assert_eq!(df.len(), 12);
+    let ids: Vec<i64> = df.apply(|p| p.id).take(12);
+    assert_eq!(ids, (0..12).collect::<Vec<i64>>());
+}
+
+#[test]
+fn test_read_parquet_dataset_glob() {
+    write_dataset("tmp/dataset_glob", 4);
+
+    let df = read_parquet_dataset::<Part>(String::from("tmp/dataset_glob/part-0000[13].parquet"))
+        .unwrap();
+
+    assert_eq!(df.len(), 6);
+    assert_eq!(df.take(1)[0].name, "part1");
+}
+
+#[test]
+fn test_read_parquet_dataset_parallel() {
+    write_dataset("tmp/dataset_parallel", 16);
+
+    let options = ParquetReadOptions::new().parallel(true);
+    let df =
+        read_parquet_dataset_with::<Part>(String::from("tmp/dataset_parallel"), &options).unwrap();
+
+    // The order of the files is kept when reading in parallel.
+    let ids: Vec<i64> = df.apply(|p| p.id).take(48);
+    assert_eq!(ids, (0..48).collect::<Vec<i64>>());
+}
+
+#[test]
+fn test_read_parquet_dataset_incompatible_schema() {
+    write_dataset("tmp/dataset_incompatible", 2);
+    DataFrame::new(vec![Other {
+        id: "a".to_string(),
+        name: "other".to_string(),
+    }])
+    .to_parquet(String::from("tmp/dataset_incompatible/part-00002.parquet"))
+    .unwrap();
+
+    let result = read_parquet_dataset::<Part>(String::from("tmp/dataset_incompatible"));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_read_parquet_dataset_reordered_and_optional_columns() {
+    write_dataset("tmp/dataset_reordered", 2);
+    DataFrame::new(vec![Reordered {
+        name: "reordered".to_string(),
+        id: Some(6),
+    }])
+    .to_parquet(String::from("tmp/dataset_reordered/part-00002.parquet"))
+    .unwrap();
+
+    let df = read_parquet_dataset::<Part>(String::from("tmp/dataset_reordered")).unwrap();
+    assert_eq!(df.len(), 7);
+    assert_eq!(
+        df.take(7)[6],
+        Part {
+            id: 6,
+            name: "reordered".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_read_parquet_dataset_no_files() {
+    let result = read_parquet_dataset::<Part>(String::from("tmp/dataset_missing/*.parquet"));
+    assert!(result.is_err());
+}
@@ -0,0 +1,230 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{
+    dataframe::DataFrame, decimal::Decimal, options::ParquetWriteOptions, predicate::Predicate,
+    read_parquet_filtered,
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Event {
+    id: i64,
+    kind: String,
+    score: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Id {
+    id: i64,
+}
+
+fn write_events(path: &str) {
+    let df = DataFrame::new(
+        (0..20)
+            .map(|i| Event {
+                id: i,
+                kind: if i < 10 { "a" } else { "b" }.to_string(),
+                score: i as f64 / 2.0,
+            })
+            .collect(),
+    );
+
+    df.to_parquet_with(
+        path.to_string(),
+        &ParquetWriteOptions::new().row_group_size(Some(5)),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_read_parquet_filtered_eq() {
+    write_events("tmp/filtered_eq.parquet");
+
+    let df = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_eq.parquet"),
+        &[Predicate::eq("id", 12)],
+    )
+    .unwrap();
+
+    assert_eq!(
+        df.take(2),
+        vec![Event {
+            id: 12,
+            kind: "b".to_string(),
+            score: 6.0,
+        }]
+    );
+}
+
+#[test]
+fn test_read_parquet_filtered_range() {
+    write_events("tmp/filtered_range.parquet");
+
+    let df = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_range.parquet"),
+        &[Predicate::gt("id", 3), Predicate::lt("id", 7)],
+    )
+    .unwrap();
+    let ids: Vec<i64> = df.apply(|e| e.id).take(10);
+    assert_eq!(ids, vec![4, 5, 6]);
+
+    let df = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_range.parquet"),
+        &[Predicate::between("score", 8.0, 9.0)],
+    )
+    .unwrap();
+    let ids: Vec<i64> = df.apply(|e| e.id).take(10);
+    assert_eq!(ids, vec![16, 17, 18]);
+}
+
+#[test]
+fn test_read_parquet_filtered_is_in() {
+    write_events("tmp/filtered_is_in.parquet");
+
+    let df = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_is_in.parquet"),
+        &[Predicate::is_in("id", vec![1, 9, 19, 42])],
+    )
+    .unwrap();
+    let ids: Vec<i64> = df.apply(|e| e.id).take(10);
+    assert_eq!(ids, vec![1, 9, 19]);
+
+    let df = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_is_in.parquet"),
+        &[
+            Predicate::is_in("kind", vec!["b", "c"]),
+            Predicate::le("id", 11),
+        ],
+    )
+    .unwrap();
+    let ids: Vec<i64> = df.apply(|e| e.id).take(10);
+    assert_eq!(ids, vec![10, 11]);
+}
+
+#[test]
+fn test_read_parquet_filtered_float_and_empty() {
+    write_events("tmp/filtered_float.parquet");
+
+    let df = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_float.parquet"),
+        &[Predicate::ge("score", 7)],
+    )
+    .unwrap();
+    assert_eq!(df.len(), 6);
+
+    let df = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_float.parquet"),
+        &[Predicate::gt("id", 100)],
+    )
+    .unwrap();
+    assert_eq!(df.len(), 0);
+}
+
+#[test]
+fn test_read_parquet_filtered_column_outside_struct() {
+    write_events("tmp/filtered_projection.parquet");
+
+    let df = read_parquet_filtered::<Id>(
+        String::from("tmp/filtered_projection.parquet"),
+        &[Predicate::eq("kind", "a"), Predicate::ge("id", 8)],
+    )
+    .unwrap();
+
+    assert_eq!(df.take(10), vec![Id { id: 8 }, Id { id: 9 }]);
+}
+
+#[test]
+fn test_read_parquet_filtered_unknown_column() {
+    write_events("tmp/filtered_unknown.parquet");
+
+    let result = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_unknown.parquet"),
+        &[Predicate::eq("kinds", "a")],
+    );
+
+    assert_eq!(
+        result.err().unwrap().message,
+        "Column kinds of the predicate not found in parquet!"
+    );
+}
+
+#[test]
+fn test_read_parquet_filtered_mismatched_type() {
+    write_events("tmp/filtered_mismatched.parquet");
+
+    let result = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_mismatched.parquet"),
+        &[Predicate::eq("id", "3")],
+    );
+    assert_eq!(
+        result.err().unwrap().message,
+        "Value Str(\"3\") of the predicate can not be compared with column id!"
+    );
+
+    let result = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_mismatched.parquet"),
+        &[Predicate::between("kind", 1, 2)],
+    );
+    assert!(result.is_err());
+
+    // Numbers of any type can be compared with each other.
+    let df = read_parquet_filtered::<Event>(
+        String::from("tmp/filtered_mismatched.parquet"),
+        &[Predicate::ge("id", 17.5), Predicate::lt("score", 10)],
+    )
+    .unwrap();
+    assert_eq!(df.len(), 2);
+}
+
+#[test]
+fn test_read_parquet_filtered_decimal() {
+    #[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+    struct Order {
+        id: i64,
+        price: Decimal,
+        total: Decimal,
+    }
+
+    let df = DataFrame::new(
+        (0..20)
+            .map(|i| Order {
+                id: i,
+                price: Decimal::new(i as i128 * 125, 2),
+                total: Decimal::new(i as i128 * 1_000_000_000_000_000_000_001, 3),
+            })
+            .collect(),
+    );
+    // A small precision is stored as integers, a large one as fixed length bytes.
+    df.to_parquet_with(
+        String::from("tmp/filtered_decimal.parquet"),
+        &ParquetWriteOptions::new()
+            .row_group_size(Some(5))
+            .decimal("price", 8, 2)
+            .decimal("total", 25, 3),
+    )
+    .unwrap();
+
+    let ids = |predicates: &[Predicate]| -> Vec<i64> {
+        read_parquet_filtered::<Order>(String::from("tmp/filtered_decimal.parquet"), predicates)
+            .unwrap()
+            .apply(|o| o.id)
+            .take(20)
+    };
+
+    assert_eq!(
+        ids(&[Predicate::eq("price", Decimal::new(1250, 2))]),
+        vec![10]
+    );
+    assert_eq!(
+        ids(&[Predicate::eq("price", Decimal::new(125, 1))]),
+        vec![10]
+    );
+    assert_eq!(ids(&[Predicate::between("price", 12, 14)]), vec![10, 11]);
+    assert_eq!(ids(&[Predicate::gt("price", 22.5)]), vec![19]);
+    assert_eq!(
+        ids(&[Predicate::ge(
+            "total",
+            Decimal::new(17_000_000_000_000_000_000_017, 3)
+        )]),
+        vec![17, 18, 19]
+    );
+}
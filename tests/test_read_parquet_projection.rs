@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{options::ParquetReadOptions, read_parquet, read_parquet_with};
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+struct Index {
+    index: i32,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+struct Renamed {
+    #[serde(rename = "index")]
+    id: i32,
+    sequence: Vec<i32>,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+struct Sequence {
+    sequence: Vec<i32>,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+struct Flatten {
+    index: i32,
+    #[serde(flatten)]
+    others: Sequence,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+struct Data {
+    name: String,
+    age: Option<u32>,
+}
+
+#[test]
+fn test_read_parquet_struct_subset() {
+    let df = read_parquet::<Index>(String::from("tests/fixtures/complex.parquet")).unwrap();
+
+    assert_eq!(df.len(), 3);
+    assert_eq!(
+        df.take(3),
+        vec![Index { index: 1 }, Index { index: 2 }, Index { index: 3 }]
+    );
+}
+
+#[test]
+fn test_read_parquet_renamed_fields() {
+    let df = read_parquet::<Renamed>(String::from("tests/fixtures/complex.parquet")).unwrap();
+
+    let row = df.find(|x| x.id == 2).unwrap();
+    assert_eq!(row.sequence, vec![2, 42]);
+}
+
+#[test]
+fn test_read_parquet_flatten_reads_all_columns() {
+    let df = read_parquet::<Flatten>(String::from("tests/fixtures/complex.parquet")).unwrap();
+
+    let row = df.find(|x| x.index == 3).unwrap();
+    assert_eq!(row.others.sequence, vec![4, 12]);
+}
+
+#[test]
+fn test_read_parquet_explicit_columns() {
+    let options = ParquetReadOptions::new().columns(&["name"]);
+    let df =
+        read_parquet_with::<Data>(String::from("tests/fixtures/basic.parquet"), &options).unwrap();
+
+    assert_eq!(df.len(), 3);
+    assert!(df.find(|x| x.age.is_some()).is_none());
+    assert!(df.find(|x| x.name == "Sergio").is_some());
+}
+
+#[test]
+fn test_read_parquet_unknown_column() {
+    let options = ParquetReadOptions::new().columns(&["name", "unknown"]);
+    match read_parquet_with::<Data>(String::from("tests/fixtures/basic.parquet"), &options) {
+        Ok(_) => panic!("read_parquet_with should return an error!"),
+        Err(e) => assert!(e.message.contains("unknown")),
+    }
+}
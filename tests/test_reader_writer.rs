@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+
+use combee::{
+    dataframe::DataFrame,
+    options::{CsvReadOptions, CsvWriteOptions},
+    read_csv_from_reader, read_parquet_from_bytes, read_parquet_from_reader, scan_csv_from_reader,
+};
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+struct Data {
+    name: String,
+    age: u32,
+}
+
+fn dataframe() -> DataFrame<Data> {
+    DataFrame::new(vec![
+        Data {
+            name: String::from("Daniel"),
+            age: 26,
+        },
+        Data {
+            name: String::from("Sergio"),
+            age: 30,
+        },
+    ])
+}
+
+#[test]
+fn test_read_csv_from_memory() {
+    let content = "name;age\nDaniel;26\nSergio;30\n";
+    let options = CsvReadOptions::new().delimiter(b';');
+    let df = read_csv_from_reader::<Data, _>(content.as_bytes(), &options).unwrap();
+
+    assert_eq!(df.take(2), dataframe().take(2));
+}
+
+#[test]
+fn test_scan_csv_from_file_reader() {
+    let file = File::open("tests/fixtures/basic.csv").unwrap();
+    let rows = scan_csv_from_reader::<Data, _>(file, &CsvReadOptions::default())
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(rows.len(), 3);
+}
+
+#[test]
+fn test_write_csv_to_memory() {
+    let mut buffer = Vec::new();
+    dataframe()
+        .write_csv(&mut buffer, &CsvWriteOptions::default())
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(buffer.clone()).unwrap(),
+        "name,age\nDaniel,26\nSergio,30\n"
+    );
+
+    let df =
+        read_csv_from_reader::<Data, _>(buffer.as_slice(), &CsvReadOptions::default()).unwrap();
+    assert_eq!(df.len(), 2);
+}
+
+#[test]
+fn test_write_csv_slice_to_memory() {
+    let df = dataframe();
+
+    let mut buffer = Vec::new();
+    df.head(1)
+        .write_csv(&mut buffer, &CsvWriteOptions::new().has_headers(false))
+        .unwrap();
+
+    assert_eq!(String::from_utf8(buffer).unwrap(), "Daniel,26\n");
+}
+
+#[test]
+fn test_write_parquet_to_memory() {
+    let mut buffer = Vec::new();
+    dataframe().write_parquet(&mut buffer).unwrap();
+
+    let df = read_parquet_from_bytes::<Data, _>(buffer).unwrap();
+    assert_eq!(df.take(2), dataframe().take(2));
+}
+
+#[test]
+fn test_read_parquet_from_file_reader() {
+    let file = File::open("tests/fixtures/basic.parquet").unwrap();
+    let df = read_parquet_from_reader::<Data, _>(file).unwrap();
+
+    assert_eq!(df.len(), 3);
+}
+
+#[test]
+fn test_read_parquet_from_invalid_reader() {
+    let content = "name,age\nDaniel,26\n";
+    assert!(read_parquet_from_bytes::<Data, _>(content.as_bytes().to_vec()).is_err());
+
+    let file = File::open("tests/fixtures/unsorted.csv").unwrap();
+    assert!(read_parquet_from_reader::<Data, _>(file).is_err());
+}
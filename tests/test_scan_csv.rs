@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{functions::all, functions::sum, options::CsvReadOptions, scan_csv, scan_csv_with};
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug)]
+struct Data {
+    name: String,
+    age: u32,
+}
+
+#[test]
+fn test_scan_csv_rows() {
+    let rows: Vec<Data> = scan_csv::<Data>(String::from("tests/fixtures/unsorted.csv"))
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect();
+
+    assert_eq!(rows.len(), 6);
+    assert_eq!(
+        rows[4],
+        Data {
+            name: String::from("Mateus"),
+            age: 31
+        }
+    );
+}
+
+#[test]
+fn test_scan_csv_chunks() {
+    let chunks = scan_csv::<Data>(String::from("tests/fixtures/unsorted.csv"))
+        .unwrap()
+        .chunks(4)
+        .unwrap()
+        .map(|c| c.unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].len(), 4);
+    assert_eq!(chunks[1].len(), 2);
+
+    let total: u32 = chunks
+        .iter()
+        .map(|df| df.groupby(all).agg(|_, g| sum(g, |x| x.age)).take(1)[0])
+        .sum();
+    assert_eq!(total, 157);
+}
+
+#[test]
+fn test_scan_csv_with_options() {
+    let options = CsvReadOptions::new().delimiter(b';');
+    let mut iter = scan_csv_with::<Data>(String::from("tests/fixtures/semicolon.csv"), &options)
+        .unwrap()
+        .chunks(10)
+        .unwrap();
+
+    assert_eq!(iter.next().unwrap().unwrap().len(), 3);
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_scan_csv_invalid_row() {
+    let results: Vec<Result<Data, _>> =
+        scan_csv::<Data>(String::from("tests/fixtures/invalid.csv"))
+            .unwrap()
+            .collect();
+
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].as_ref().unwrap_err().message.contains("string"));
+    assert!(results[2].is_ok());
+}
+
+#[test]
+fn test_scan_csv_chunks_errors() {
+    let result = scan_csv::<Data>(String::from("tests/fixtures/unsorted.csv"))
+        .unwrap()
+        .chunks(0);
+    assert_eq!(
+        result.err().unwrap().message,
+        "Chunk size must be greater than zero!"
+    );
+
+    // The rows read before the invalid one are returned before the error.
+    let chunks: Vec<_> = scan_csv::<Data>(String::from("tests/fixtures/invalid.csv"))
+        .unwrap()
+        .chunks(2)
+        .unwrap()
+        .collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].as_ref().unwrap().len(), 1);
+    assert!(chunks[1].is_err());
+    assert_eq!(chunks[2].as_ref().unwrap().len(), 1);
+}
+
+#[test]
+fn test_scan_csv_chunks_keep_rows_around_errors() {
+    std::fs::write(
+        "tmp/chunks_invalid.csv",
+        "name,age\nA,1\nB,2\nC,bad\nD,4\nE,5\nF,6\nG,7\n",
+    )
+    .unwrap();
+
+    let chunks: Vec<_> = scan_csv::<Data>(String::from("tmp/chunks_invalid.csv"))
+        .unwrap()
+        .chunks(4)
+        .unwrap()
+        .collect();
+
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks[1].is_err());
+    let names: Vec<String> = chunks
+        .into_iter()
+        .filter_map(|c| c.ok())
+        .flat_map(|df| df.take(df.len()))
+        .map(|r| r.name)
+        .collect();
+    assert_eq!(names, vec!["A", "B", "D", "E", "F", "G"]);
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use combee::dataframe::DataFrame;
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Order {
+    user_id: u32,
+    amount: f64,
+}
+
+fn users() -> DataFrame<User> {
+    DataFrame::new(
+        ["Daniel", "Sergio", "Ana", "Bia"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| User {
+                id: i as u32 + 1,
+                name: name.to_string(),
+            })
+            .collect(),
+    )
+}
+
+fn orders() -> DataFrame<Order> {
+    DataFrame::new(vec![
+        Order {
+            user_id: 3,
+            amount: 1.0,
+        },
+        Order {
+            user_id: 1,
+            amount: 10.0,
+        },
+        Order {
+            user_id: 1,
+            amount: 2.5,
+        },
+        Order {
+            user_id: 7,
+            amount: 7.5,
+        },
+    ])
+}
+
+fn names(df: &DataFrame<User>) -> Vec<String> {
+    df.take(df.len()).into_iter().map(|u| u.name).collect()
+}
+
+#[test]
+fn test_semi_join() {
+    let df = users().semi_join(&orders(), |u| u.id, |o| o.user_id);
+
+    // Each row is kept once even with several matches, in the original order.
+    assert_eq!(names(&df), vec!["Daniel", "Ana"]);
+}
+
+#[test]
+fn test_anti_join() {
+    let df = users().anti_join(&orders(), |u| u.id, |o| o.user_id);
+
+    assert_eq!(names(&df), vec!["Sergio", "Bia"]);
+}
+
+#[test]
+fn test_semi_and_anti_join_partition_rows() {
+    let big = DataFrame::new((0..10_000u32).collect());
+    let evens = DataFrame::new((0..10_000u32).filter(|i| i % 2 == 0).collect());
+
+    let semi = big.semi_join(&evens, |i| *i, |i| *i);
+    let anti = big.anti_join(&evens, |i| *i, |i| *i);
+
+    assert_eq!(semi.len(), 5_000);
+    assert_eq!(anti.len(), 5_000);
+    assert!(anti.find(|i| i % 2 == 0).is_none());
+}
+
+#[test]
+fn test_semi_join_empty_and_derived_keys() {
+    let empty = DataFrame::<Order>::new(vec![]);
+    assert_eq!(users().semi_join(&empty, |u| u.id, |o| o.user_id).len(), 0);
+    assert_eq!(users().anti_join(&empty, |u| u.id, |o| o.user_id).len(), 4);
+
+    let initials = DataFrame::new(vec!['S', 'B']);
+    let df = users().semi_join(&initials, |u| u.name.chars().next(), |c| Some(*c));
+    assert_eq!(names(&df), vec!["Sergio", "Bia"]);
+}
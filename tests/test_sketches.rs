@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{
+    dataframe::DataFrame,
+    functions::{all, nunique, quantile, Interpolation},
+    sketches::{hyperloglog, tdigest, HyperLogLog, TDigest},
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Visit {
+    user: u32,
+    latency: f64,
+}
+
+fn visits() -> DataFrame<Visit> {
+    DataFrame::new(
+        (0..100_000u64)
+            .map(|i| Visit {
+                user: ((i * 7919) % 30_000) as u32,
+                latency: ((i * 104_729) % 10_000) as f64 / 10.0,
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_hyperloglog_estimate_close_to_nunique() {
+    let result = visits().groupby(all).agg(|_, g| {
+        (
+            nunique(g, |v| v.user),
+            hyperloglog(g, 14, |v| v.user).unwrap().estimate(),
+        )
+    });
+
+    let (exact, estimate) = result.take(1)[0];
+    assert_eq!(exact, 30_000);
+    let error = (estimate as f64 - exact as f64).abs() / exact as f64;
+    assert!(error < 0.03, "relative error {}", error);
+}
+
+#[test]
+fn test_hyperloglog_merge_and_serialize() {
+    let mut first = HyperLogLog::new(12).unwrap();
+    let mut second = HyperLogLog::new(12).unwrap();
+    for i in 0..5000 {
+        first.insert(&format!("user-{}", i));
+        second.insert(&format!("user-{}", i + 2500));
+    }
+
+    // Partial sketches are written, read back later and merged.
+    let json = serde_json::to_string(&second).unwrap();
+    let restored: HyperLogLog = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, second);
+
+    first.merge(&restored).unwrap();
+    let error = (first.estimate() as f64 - 7500.0).abs() / 7500.0;
+    assert!(error < 0.05, "relative error {}", error);
+
+    let mut other = HyperLogLog::new(10).unwrap();
+    assert!(other.merge(&first).is_err());
+    assert!(HyperLogLog::new(3).is_err());
+    assert!(HyperLogLog::new(17).is_err());
+    assert_eq!(HyperLogLog::default().estimate(), 0);
+}
+
+#[test]
+fn test_tdigest_quantiles_close_to_exact() {
+    let result = visits().groupby(all).agg(|_, g| {
+        let sketch = tdigest(g, 100.0, |v| v.latency).unwrap();
+        [0.01, 0.5, 0.99]
+            .iter()
+            .map(|q| {
+                (
+                    quantile(g, *q, Interpolation::Linear, |v| v.latency).unwrap(),
+                    sketch.quantile(*q).unwrap(),
+                )
+            })
+            .collect::<Vec<(f64, f64)>>()
+    });
+
+    for (exact, estimate) in result.take(1)[0].iter() {
+        // Latencies go from 0 to 1000, so 5 is half a percent of the range.
+        assert!((exact - estimate).abs() < 5.0, "{} != {}", exact, estimate);
+    }
+
+    let df = visits();
+    let grouped = df.groupby(all);
+    let (_, group) = grouped.groups().next().unwrap();
+    let sketch = tdigest(&group, 100.0, |v| v.latency).unwrap();
+    assert_eq!(sketch.count(), 100_000);
+    assert_eq!(sketch.quantile(0.0), Some(0.0));
+    assert_eq!(sketch.quantile(1.0), Some(999.9));
+    assert!(sketch.centroids().len() <= 100);
+}
+
+#[test]
+fn test_tdigest_merge_chunks_and_serialize() {
+    let mut chunks: Vec<TDigest> = Vec::new();
+    for chunk in 0..4 {
+        let mut sketch = TDigest::default();
+        for i in 0..25_000 {
+            sketch.insert((chunk * 25_000 + i) as f64);
+        }
+        let json = serde_json::to_string(&sketch).unwrap();
+        chunks.push(serde_json::from_str(&json).unwrap());
+    }
+
+    let mut merged = TDigest::default();
+    for chunk in chunks.iter() {
+        merged.merge(chunk).unwrap();
+    }
+
+    assert_eq!(merged.count(), 100_000);
+    for q in [0.1, 0.5, 0.9] {
+        let estimate = merged.quantile(q).unwrap();
+        assert!((estimate - q * 100_000.0).abs() < 500.0, "{}", estimate);
+    }
+}
+
+#[test]
+fn test_tdigest_edge_cases() {
+    let mut sketch = TDigest::new(50.0).unwrap();
+    assert_eq!(sketch.quantile(0.5), None);
+
+    sketch.insert(f64::NAN);
+    sketch.insert(3.0);
+    assert_eq!(sketch.count(), 1);
+    assert_eq!(sketch.quantile(0.5), Some(3.0));
+
+    sketch.insert(5.0);
+    assert_eq!(sketch.quantile(0.0), Some(3.0));
+    assert_eq!(sketch.quantile(0.5), Some(4.0));
+    assert_eq!(sketch.quantile(1.0), Some(5.0));
+    assert_eq!(sketch.quantile(1.5), None);
+
+    assert!(TDigest::new(5.0).is_err());
+}
+
+#[test]
+fn test_sketches_invalid_serialization() {
+    let json = serde_json::to_string(&HyperLogLog::new(4).unwrap()).unwrap();
+    assert!(serde_json::from_str::<HyperLogLog>(&json).is_ok());
+    let zero = json.replace("\"precision\":4", "\"precision\":0");
+    assert!(serde_json::from_str::<HyperLogLog>(&zero).is_err());
+    let wider = json.replace("\"precision\":4", "\"precision\":5");
+    assert!(serde_json::from_str::<HyperLogLog>(&wider).is_err());
+
+    let json = serde_json::to_string(&TDigest::new(20.0).unwrap()).unwrap();
+    assert!(serde_json::from_str::<TDigest>(&json).is_ok());
+    let low = json.replace("\"compression\":20.0", "\"compression\":1.0");
+    assert!(serde_json::from_str::<TDigest>(&low).is_err());
+}
+
+#[test]
+fn test_tdigest_empty_round_trip_and_merge() {
+    let empty = TDigest::default();
+    let json = serde_json::to_string(&empty).unwrap();
+    let restored: TDigest = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, empty);
+    assert_eq!(restored.quantile(0.5), None);
+
+    let mut sketch = TDigest::default();
+    sketch.insert(2.0);
+    sketch.merge(&restored).unwrap();
+    assert_eq!(sketch.quantile(0.5), Some(2.0));
+
+    let mut other = TDigest::new(50.0).unwrap();
+    assert!(other.merge(&sketch).is_err());
+}
+
+#[test]
+fn test_tdigest_ignores_infinite_values() {
+    let mut sketch = TDigest::default();
+    sketch.insert(1.0);
+    sketch.insert(f64::INFINITY);
+    sketch.insert(f64::NEG_INFINITY);
+    assert_eq!(sketch.count(), 1);
+
+    let json = serde_json::to_string(&sketch).unwrap();
+    let restored: TDigest = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, sketch);
+    assert_eq!(restored.count(), 1);
+
+    let mut merged = TDigest::default();
+    merged.insert(3.0);
+    merged.merge(&restored).unwrap();
+    assert_eq!(merged.quantile(0.0), Some(1.0));
+    assert_eq!(merged.quantile(0.5), Some(2.0));
+
+    // The compressed centroids cached by a quantile are refreshed by the next insert.
+    merged.insert(5.0);
+    assert_eq!(merged.quantile(1.0), Some(5.0));
+    assert_eq!(merged.centroids().len(), 3);
+}
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+use combee::{
+    dataframe::DataFrame,
+    functions::{all, kurtosis, median, mode, quantile, skew, std, variance, Interpolation},
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct D {
+    group: u32,
+    value: f64,
+}
+
+fn df() -> DataFrame<D> {
+    DataFrame::new(
+        [1.0, 2.0, 3.0, 4.0, 100.0]
+            .iter()
+            .map(|v| D {
+                group: 0,
+                value: *v,
+            })
+            .collect(),
+    )
+}
+
+fn close(a: Option<f64>, b: f64) -> bool {
+    (a.unwrap() - b).abs() < 1e-9
+}
+
+#[test]
+fn test_median_and_quantiles() {
+    let result = df()
+        .groupby(all)
+        .agg(|_, g| {
+            (
+                median(g, |d| d.value),
+                quantile(g, 0.3, Interpolation::Linear, |d| d.value),
+                quantile(g, 0.3, Interpolation::Lower, |d| d.value),
+                quantile(g, 0.3, Interpolation::Higher, |d| d.value),
+                quantile(g, 0.3, Interpolation::Nearest, |d| d.value),
+                quantile(g, 0.3, Interpolation::Midpoint, |d| d.value),
+                quantile(g, 1.0, Interpolation::Linear, |d| d.value),
+            )
+        })
+        .take(1);
+
+    assert_eq!(
+        result[0],
+        (
+            Some(3.0),
+            Some(2.2),
+            Some(2.0),
+            Some(3.0),
+            Some(2.0),
+            Some(2.5),
+            Some(100.0)
+        )
+    );
+
+    let even = DataFrame::new(vec![4.0, 1.0, 3.0, 2.0]);
+    let result = even
+        .groupby(all)
+        .agg(|_, g| {
+            (
+                median(g, |x| *x),
+                quantile(g, 0.5, Interpolation::Nearest, |x| *x),
+            )
+        })
+        .take(1);
+    // Position 1.5 is a tie, the even position (2) is used.
+    assert_eq!(result[0], (Some(2.5), Some(3.0)));
+}
+
+#[test]
+fn test_variance_and_std() {
+    let result = df()
+        .groupby(all)
+        .agg(|_, g| {
+            (
+                variance(g, 1, |d| d.value),
+                std(g, 0, |d| d.value),
+                variance(g, 5, |d| d.value),
+            )
+        })
+        .take(1);
+
+    assert!(close(result[0].0, 1902.5));
+    assert!(close(result[0].1, 39.01281840626232));
+    assert_eq!(result[0].2, None);
+}
+
+#[test]
+fn test_skew_and_kurtosis() {
+    let result = df()
+        .groupby(all)
+        .agg(|_, g| (skew(g, |d| d.value), kurtosis(g, |d| d.value)))
+        .take(1);
+
+    assert!(close(result[0].0, 2.232395911636458));
+    assert!(close(result[0].1, 4.986865957200655));
+
+    let constant = DataFrame::new(vec![2.0; 6]);
+    let result = constant
+        .groupby(all)
+        .agg(|_, g| (skew(g, |x| *x), kurtosis(g, |x| *x)))
+        .take(1);
+    assert_eq!(result[0], (Some(0.0), Some(0.0)));
+
+    let small = DataFrame::new(vec![1.0, 2.0, 3.0]);
+    let result = small
+        .groupby(all)
+        .agg(|_, g| (skew(g, |x| *x), kurtosis(g, |x| *x)))
+        .take(1);
+    assert_eq!(result[0], (Some(0.0), None));
+}
+
+#[test]
+fn test_mode() {
+    let df = DataFrame::new(vec![3, 1, 1, 3, 2, 3, 1]);
+    let result = df.groupby(all).agg(|_, g| mode(g, |x| *x)).take(1);
+    // 3 and 1 appear three times, 3 appears first.
+    assert_eq!(result[0], Some(3));
+
+    let words = DataFrame::new(vec!["b".to_string(), "a".to_string(), "a".to_string()]);
+    let result = words
+        .groupby(all)
+        .agg(|_, g| mode(g, |x| x.clone()))
+        .take(1);
+    assert_eq!(result[0], Some("a".to_string()));
+}
+
+#[test]
+fn test_statistics_single_row_group() {
+    let one = df().filter(|d| d.value > 50.0);
+    let result = one
+        .groupby(|d| d.group)
+        .agg(|_, g| {
+            (
+                variance(g, 1, |d| d.value),
+                variance(g, 0, |d| d.value),
+                quantile(g, 1.5, Interpolation::Linear, |d| d.value),
+                median(g, |d| d.value),
+                skew(g, |d| d.value),
+            )
+        })
+        .take(1);
+    assert_eq!(result[0], (None, Some(0.0), None, Some(100.0), None));
+}
+
+#[test]
+fn test_quantile_nan_sorted_last() {
+    // A NaN with the sign bit set, as produced by 0.0 / 0.0 on some platforms.
+    let negative_nan = f64::from_bits(f64::NAN.to_bits() | (1 << 63));
+    let df = DataFrame::new(vec![3.0, negative_nan, 1.0, f64::NAN, 2.0]);
+
+    let result = df
+        .groupby(all)
+        .agg(|_, g| {
+            (
+                quantile(g, 0.0, Interpolation::Lower, |x| *x),
+                quantile(g, 0.5, Interpolation::Lower, |x| *x),
+                quantile(g, 1.0, Interpolation::Lower, |x| *x),
+            )
+        })
+        .take(1);
+
+    assert_eq!(result[0].0, Some(1.0));
+    assert_eq!(result[0].1, Some(3.0));
+    assert!(result[0].2.unwrap().is_nan());
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use combee::{
+    dataframe::DataFrame,
+    options::{CsvWriteOptions, LineTerminator, QuoteStyle},
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+struct D {
+    index: String,
+    x: f64,
+    y: Option<u32>,
+}
+
+fn dataframe() -> DataFrame<D> {
+    DataFrame::new(vec![
+        D {
+            index: String::from("xpto, jujuba"),
+            x: 1.23456,
+            y: Some(3),
+        },
+        D {
+            index: String::from("rainbow"),
+            x: -0.1,
+            y: None,
+        },
+    ])
+}
+
+#[test]
+fn test_to_csv_tsv_without_quotes() {
+    let path = String::from("tmp/options_tsv.csv");
+
+    let options = CsvWriteOptions::new()
+        .delimiter(b'\t')
+        .quote_style(QuoteStyle::Never);
+    dataframe().to_csv_with(path.clone(), &options).unwrap();
+
+    let content = fs::read_to_string(path).unwrap();
+    assert_eq!(
+        content,
+        "index\tx\ty\nxpto, jujuba\t1.23456\t3\nrainbow\t-0.1\t\n"
+    );
+}
+
+#[test]
+fn test_to_csv_float_precision_and_null_value() {
+    let path = String::from("tmp/options_precision.csv");
+
+    let options = CsvWriteOptions::new()
+        .float_precision(Some(2))
+        .null_value("NULL")
+        .line_terminator(LineTerminator::Crlf);
+    dataframe().to_csv_with(path.clone(), &options).unwrap();
+
+    let content = fs::read_to_string(path).unwrap();
+    assert_eq!(
+        content,
+        "index,x,y\r\n\"xpto, jujuba\",1.23,3\r\nrainbow,-0.10,NULL\r\n"
+    );
+}
+
+#[test]
+fn test_to_csv_append_without_repeating_header() {
+    let path = String::from("tmp/options_append.csv");
+    let _ = fs::remove_file(&path);
+
+    let options = CsvWriteOptions::new().append(true);
+    let df = dataframe();
+    df.head(1).to_csv_with(path.clone(), &options).unwrap();
+    df.to_csv_with(path.clone(), &options).unwrap();
+
+    let content = fs::read_to_string(path.clone()).unwrap();
+    assert_eq!(
+        content,
+        "index,x,y\n\"xpto, jujuba\",1.23456,3\n\"xpto, jujuba\",1.23456,3\nrainbow,-0.1,\n"
+    );
+
+    let new_df = combee::read_csv::<D>(path).unwrap();
+    assert_eq!(new_df.len(), 3);
+}
+
+#[test]
+fn test_to_csv_without_header() {
+    let path = String::from("tmp/options_no_header.csv");
+
+    let options = CsvWriteOptions::new()
+        .has_headers(false)
+        .quote_style(QuoteStyle::NonNumeric);
+    dataframe()
+        .head(1)
+        .to_csv_with(path.clone(), &options)
+        .unwrap();
+
+    let content = fs::read_to_string(path).unwrap();
+    assert_eq!(content, "\"xpto, jujuba\",1.23456,3\n");
+}
@@ -0,0 +1,128 @@
+use parquet::{
+    basic::{Compression, Encoding},
+    file::reader::{FileReader, SerializedFileReader},
+};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+
+use combee::{
+    dataframe::DataFrame,
+    options::{ParquetCompression, ParquetWriteOptions},
+    read_parquet,
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct A {
+    name: String,
+    value: f64,
+}
+
+fn dataframe() -> DataFrame<A> {
+    DataFrame::new(
+        (0..10)
+            .map(|i| A {
+                name: format!("name{}", i % 3),
+                value: i as f64,
+            })
+            .collect(),
+    )
+}
+
+fn metadata_reader(path: &str) -> SerializedFileReader<File> {
+    SerializedFileReader::new(File::open(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_to_parquet_compressions() {
+    let codecs = [
+        (ParquetCompression::Snappy, Compression::SNAPPY),
+        (
+            ParquetCompression::Zstd(Some(3)),
+            Compression::ZSTD(Default::default()),
+        ),
+        (
+            ParquetCompression::Gzip(None),
+            Compression::GZIP(Default::default()),
+        ),
+        (ParquetCompression::Lz4, Compression::LZ4_RAW),
+    ];
+
+    for (i, (codec, expected)) in codecs.into_iter().enumerate() {
+        let path = format!("tmp/compression_{}.parquet", i);
+        let options = ParquetWriteOptions::new().compression(codec);
+        dataframe().to_parquet_with(path.clone(), &options).unwrap();
+
+        let reader = metadata_reader(&path);
+        let column = reader.metadata().row_group(0).column(0);
+        assert_eq!(
+            std::mem::discriminant(&column.compression()),
+            std::mem::discriminant(&expected)
+        );
+
+        let new_df = read_parquet::<A>(path).unwrap();
+        assert_eq!(new_df.take(10), dataframe().take(10));
+    }
+}
+
+#[test]
+fn test_to_parquet_invalid_compression_level() {
+    let options = ParquetWriteOptions::new().compression(ParquetCompression::Zstd(Some(100)));
+    assert!(dataframe()
+        .to_parquet_with(String::from("tmp/invalid_level.parquet"), &options)
+        .is_err());
+}
+
+#[test]
+fn test_to_parquet_row_groups_and_statistics() {
+    let path = String::from("tmp/row_groups.parquet");
+    let options = ParquetWriteOptions::new().row_group_size(Some(4));
+    dataframe().to_parquet_with(path.clone(), &options).unwrap();
+
+    let reader = metadata_reader(&path);
+    assert_eq!(reader.metadata().num_row_groups(), 3);
+    assert_eq!(reader.metadata().row_group(0).num_rows(), 4);
+    assert_eq!(reader.metadata().row_group(2).num_rows(), 2);
+    assert!(reader
+        .metadata()
+        .row_group(1)
+        .column(1)
+        .statistics()
+        .is_some());
+
+    let new_df = read_parquet::<A>(path).unwrap();
+    assert_eq!(new_df.take(10), dataframe().take(10));
+}
+
+#[test]
+fn test_to_parquet_without_statistics() {
+    let path = String::from("tmp/no_statistics.parquet");
+    let options = ParquetWriteOptions::new().statistics(false);
+    dataframe().to_parquet_with(path.clone(), &options).unwrap();
+
+    let reader = metadata_reader(&path);
+    assert!(reader
+        .metadata()
+        .row_group(0)
+        .column(1)
+        .statistics()
+        .is_none());
+}
+
+#[test]
+fn test_to_parquet_dictionary_encoding() {
+    let path = String::from("tmp/dictionary.parquet");
+    let options = ParquetWriteOptions::new()
+        .dictionary_encoding(true)
+        .data_page_size(Some(1024));
+    dataframe()
+        .head(6)
+        .to_parquet_with(path.clone(), &options)
+        .unwrap();
+
+    let reader = metadata_reader(&path);
+    let encodings = reader.metadata().row_group(0).column(0).encodings().clone();
+    assert!(encodings.contains(&Encoding::RLE_DICTIONARY));
+
+    let new_df = read_parquet::<A>(path).unwrap();
+    assert_eq!(new_df.take(10), dataframe().take(6));
+}
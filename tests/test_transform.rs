@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+use combee::{
+    dataframe::DataFrame,
+    functions::{avg, count, sum},
+};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+struct Sale {
+    store: String,
+    amount: f64,
+}
+
+fn sales() -> DataFrame<Sale> {
+    DataFrame::new(
+        [("a", 1.0), ("b", 5.0), ("a", 3.0), ("c", 2.0), ("b", 15.0)]
+            .iter()
+            .map(|(store, amount)| Sale {
+                store: store.to_string(),
+                amount: *amount,
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_transform_share_of_total() {
+    let shares = sales().groupby(|s| s.store.clone()).transform(|_, g| {
+        let total = sum(g, |s| s.amount);
+        move |s: &Sale| s.amount / total
+    });
+
+    assert_eq!(shares.take(5), vec![0.25, 0.25, 0.75, 1.0, 0.75]);
+}
+
+#[test]
+fn test_transform_z_score() {
+    let scores = sales().groupby(|s| s.store.clone()).transform(|_, g| {
+        let mean: f64 = avg(g, |s| s.amount);
+        let variance: f64 = avg(g, |s| (s.amount - mean).powi(2));
+        let std = variance.sqrt();
+        move |s: &Sale| {
+            if std == 0.0 {
+                0.0
+            } else {
+                (s.amount - mean) / std
+            }
+        }
+    });
+
+    assert_eq!(scores.take(5), vec![-1.0, -1.0, 1.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_transform_key_and_row() {
+    let labels = sales().groupby(|s| s.store.clone()).transform(|store, g| {
+        let n = count(g);
+        let store = store.clone();
+        move |s: &Sale| format!("{}/{}:{}", store, n, s.amount)
+    });
+
+    assert_eq!(
+        labels.take(5),
+        vec!["a/2:1", "b/2:5", "a/2:3", "c/1:2", "b/2:15"]
+    );
+}
+
+#[test]
+fn test_transform_aggregates_once_per_group() {
+    let calls = Cell::new(0);
+    let df = DataFrame::new((0..100).collect::<Vec<i64>>());
+
+    let totals = df.groupby(|x| x % 3).transform(|_, g| {
+        calls.set(calls.get() + 1);
+        let total = sum(g, |x| *x);
+        move |_: &i64| total
+    });
+
+    assert_eq!(calls.get(), 3);
+    assert_eq!(totals.len(), 100);
+    assert_eq!(totals.take(3), vec![1683, 1617, 1650]);
+
+    let empty = DataFrame::<i64>::new(vec![]);
+    let out = empty.groupby(|x| *x).transform(|_, _| |x: &i64| *x);
+    assert_eq!(out.len(), 0);
+}